@@ -0,0 +1,88 @@
+//! This example shows how to edit map tiles at runtime: click on a tile to dig it.
+
+use bevy::prelude::*;
+use bevy_ecs_tiled::prelude::*;
+use bevy_ecs_tilemap::prelude::*;
+
+mod helper;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.build().set(ImagePlugin::default_nearest()))
+        .add_plugins(TiledMapPlugin::default())
+        .add_plugins(helper::HelperPlugin)
+        .add_systems(Startup, startup)
+        .add_systems(Update, dig_tiles)
+        .run();
+}
+
+fn startup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn(Camera2d);
+    commands.spawn(Text(String::from(
+        "Left click: dig a tile\nRight click: replace a tile with the first tile of its tileset",
+    )));
+    commands.spawn((
+        TiledMapHandle(asset_server.load("maps/orthogonal/finite.tmx")),
+        TiledMapAnchor::Center,
+    ));
+}
+
+fn dig_tiles(
+    mut commands: Commands,
+    buttons: Res<ButtonInput<MouseButton>>,
+    window_query: Query<&Window>,
+    camera_query: Query<(&Camera, &GlobalTransform)>,
+    mut tilemap_query: Query<
+        (
+            &mut TileStorage,
+            &TilemapSize,
+            &TilemapGridSize,
+            &TilemapType,
+            &GlobalTransform,
+        ),
+        With<TiledMapTileLayerForTileset>,
+    >,
+) {
+    let dig = buttons.just_pressed(MouseButton::Left);
+    let replace = buttons.just_pressed(MouseButton::Right);
+    if !dig && !replace {
+        return;
+    }
+
+    // Convert cursor position to world space
+    let Some(cursor) = window_query
+        .get_single()
+        .ok()
+        .and_then(|window| window.cursor_position())
+    else {
+        return;
+    };
+    let Ok((camera, camera_transform)) = camera_query.get_single() else {
+        return;
+    };
+    let Ok(world_position) = camera.viewport_to_world_2d(camera_transform, cursor) else {
+        return;
+    };
+
+    for (mut storage, map_size, grid_size, map_type, tilemap_transform) in tilemap_query.iter_mut()
+    {
+        // Tiles positions are relative to their tilemap
+        let local_position = tilemap_transform
+            .affine()
+            .inverse()
+            .transform_point3(world_position.extend(0.))
+            .truncate();
+        let Some(tile_pos) =
+            TilePos::from_world_pos(&local_position, map_size, grid_size, map_type)
+        else {
+            continue;
+        };
+        if dig {
+            if let Some(entity) = remove_tile(&mut commands, &mut storage, tile_pos) {
+                info!("Dig tile {:?} at {:?}", entity, tile_pos);
+            }
+        } else if set_tile(&mut commands, &storage, tile_pos, 0, TileFlip::default()).is_some() {
+            info!("Replace tile at {:?}", tile_pos);
+        }
+    }
+}
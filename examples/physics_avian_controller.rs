@@ -113,4 +113,19 @@ impl TiledPhysicsBackend for MyCustomAvianPhysicsBackend {
         }
         colliders
     }
+
+    fn make_sensor(&self, commands: &mut Commands, collider_entity: Entity) {
+        self.0.make_sensor(commands, collider_entity);
+    }
+
+    fn set_material(
+        &self,
+        commands: &mut Commands,
+        collider_entity: Entity,
+        friction: Option<f32>,
+        restitution: Option<f32>,
+    ) {
+        self.0
+            .set_material(commands, collider_entity, friction, restitution);
+    }
 }
@@ -61,7 +61,7 @@ impl TiledPhysicsBackend for MyCustomPhysicsBackend {
                     transform: Transform::default(),
                 }]
             }
-            TiledCollider::TilesLayer { layer_id: _ } => {
+            TiledCollider::TilesLayer { .. } => {
                 vec![TiledColliderSpawnInfos {
                     name: String::from("Custom[TilesLayer]"),
                     entity: commands
@@ -0,0 +1,92 @@
+//! This example shows how to make tiles colliders behave as one-way platforms using Rapier physics.
+//! You can move the object using arrow keys: it can jump through platforms from below but lands on them from above.
+
+use bevy::prelude::*;
+use bevy_ecs_tiled::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+mod helper;
+
+const MOVE_SPEED: f32 = 200.;
+const JUMP_SPEED: f32 = 400.;
+const GRAVITY_SCALE: f32 = 10.0;
+
+fn main() {
+    App::new()
+        // Bevy default plugins: prevent blur effect by changing default sampling
+        .add_plugins(DefaultPlugins.build().set(ImagePlugin::default_nearest()))
+        // Add bevy_ecs_tiled plugin: bevy_ecs_tilemap::TilemapPlugin will
+        // be automatically added as well if it's not already done
+        .add_plugins(TiledMapPlugin::default())
+        // Examples helper plugins, such as the logic to pan and zoom the camera
+        // This should not be used directly in your game (but you can always have a look)
+        .add_plugins(helper::HelperPlugin)
+        // bevy_ecs_tiled physics plugin: this is where we select which physics backend to use
+        .add_plugins(TiledPhysicsPlugin::<TiledPhysicsRapierBackend>::default())
+        // Rapier physics plugins: one-way platforms require to use our physics hooks
+        .add_plugins(RapierPhysicsPlugin::<TiledOneWayPlatformHooks>::pixels_per_meter(100.0))
+        .add_plugins(RapierDebugRenderPlugin::default())
+        // Add our systems and run the app!
+        .add_systems(Startup, startup)
+        .add_systems(Update, move_player)
+        .run();
+}
+
+fn startup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn(Camera2d);
+    commands.spawn(Text(String::from(
+        "Move the ball using arrow keys: it can jump through platforms from below!",
+    )));
+    commands
+        .spawn((
+            TiledMapHandle::from_path("maps/orthogonal/finite.tmx", &asset_server),
+            TiledMapAnchor::Center,
+            // All tiles colliders from this layer will behave as one-way platforms
+            TiledPhysicsSettings::<TiledPhysicsRapierBackend> {
+                objects_layer_filter: TiledName::None,
+                backend: TiledPhysicsRapierBackend {
+                    one_way_platform_layer_names: vec![String::from("Tile Layer 1")],
+                },
+                ..default()
+            },
+        ))
+        // Wait for map loading to complete and spawn a simple player-controlled object
+        .observe(|_: Trigger<TiledMapCreated>, mut commands: Commands| {
+            commands.spawn((
+                RigidBody::Dynamic,
+                PlayerMarker,
+                Name::new("PlayerControlledObject (Rapier physics)"),
+                Collider::ball(10.),
+                Velocity::zero(),
+                GravityScale(GRAVITY_SCALE),
+                Transform::from_xyz(0., -50., 0.),
+            ));
+        });
+}
+
+// A 'player' marker component
+#[derive(Default, Clone, Component)]
+pub struct PlayerMarker;
+
+fn move_player(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut player: Query<&mut Velocity, With<PlayerMarker>>,
+) {
+    for mut rb_vels in player.iter_mut() {
+        let mut direction = 0.;
+
+        if keyboard_input.pressed(KeyCode::ArrowRight) {
+            direction += 1.;
+        }
+
+        if keyboard_input.pressed(KeyCode::ArrowLeft) {
+            direction -= 1.;
+        }
+
+        rb_vels.linvel.x = direction * MOVE_SPEED;
+
+        if keyboard_input.just_pressed(KeyCode::ArrowUp) {
+            rb_vels.linvel.y = JUMP_SPEED;
+        }
+    }
+}
@@ -65,7 +65,7 @@ fn input(mut chunking: Query<&mut TiledWorldChunking>, keys: Res<ButtonInput<Key
 
     if keys.pressed(KeyCode::Minus) {
         // Decrease the chunking size
-        if let Some(c) = chunking.0 {
+        if let Some(ChunkingHalfExtents(c)) = chunking.0 {
             if (c.x - STEP_SIZE as f32) > 0. {
                 *chunking = TiledWorldChunking::new(c.x - STEP_SIZE as f32, c.y - STEP_SIZE as f32);
             }
@@ -73,7 +73,7 @@ fn input(mut chunking: Query<&mut TiledWorldChunking>, keys: Res<ButtonInput<Key
     }
 
     if keys.pressed(KeyCode::Equal) {
-        if let Some(c) = chunking.0 {
+        if let Some(ChunkingHalfExtents(c)) = chunking.0 {
             if c.x < f32::MAX - STEP_SIZE as f32 {
                 *chunking = TiledWorldChunking::new(c.x + STEP_SIZE as f32, c.y + STEP_SIZE as f32);
             }
@@ -90,8 +90,10 @@ fn text_update_system(
     };
 
     for mut span in &mut query {
-        span.0 = chunking.0.map_or(String::from("None"), |chunking| {
-            format!("{}x{}", chunking.x, chunking.y)
-        });
+        span.0 = chunking
+            .0
+            .map_or(String::from("None"), |ChunkingHalfExtents(chunking)| {
+                format!("{}x{}", chunking.x, chunking.y)
+            });
     }
 }
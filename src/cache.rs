@@ -4,6 +4,20 @@ use bevy::prelude::*;
 use std::sync::{Arc, RwLock};
 use tiled::{DefaultResourceCache, ResourceCache};
 
+/// Shared cache for Tiled resources (tilesets and templates).
+///
+/// Inner cache is behind an [Arc] + [RwLock] so this type is both [Send] and [Sync]:
+/// it can be cloned and used from several threads, for instance from asset loaders
+/// running on the `AsyncComputeTaskPool`.
+///
+/// A single instance is inserted as a [Resource] by the [TiledMapPlugin](crate::TiledMapPlugin)
+/// and map and world loaders hold a clone of it: since clones share the same inner cache,
+/// a tileset or template used by several maps is only parsed once. The cache is cleared when
+/// a map or a tileset is modified, so hot-reloaded files are parsed again.
+///
+/// Note that `tiled::ResourceCache` is not object-safe, which is why we wrap the concrete
+/// [DefaultResourceCache] rather than a `dyn tiled::ResourceCache`.
+/// Locks are only held for the duration of a single cache operation.
 #[derive(Resource, Clone)]
 pub(crate) struct TiledResourceCache(pub(crate) Arc<RwLock<DefaultResourceCache>>);
 
@@ -51,3 +65,43 @@ impl ResourceCache for TiledResourceCache {
         self.0.write().unwrap().insert_template(path, template);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn cache_is_send_and_sync() {
+        assert_send_sync::<TiledResourceCache>();
+    }
+
+    #[test]
+    fn concurrent_access() {
+        let cache = TiledResourceCache::new();
+
+        let reader = {
+            let cache = cache.clone();
+            thread::spawn(move || {
+                for _ in 0..1000 {
+                    assert!(cache.get_tileset("tileset.tsx").is_none());
+                    assert!(cache.get_template("template.tx").is_none());
+                }
+            })
+        };
+        let writer = {
+            let mut cache = cache.clone();
+            thread::spawn(move || {
+                for _ in 0..1000 {
+                    cache.clear();
+                }
+            })
+        };
+
+        reader.join().unwrap();
+        writer.join().unwrap();
+        assert!(cache.get_tileset("tileset.tsx").is_none());
+    }
+}
@@ -67,23 +67,31 @@ impl Plugin for TiledDebugWorldChunkPlugin {
 }
 
 fn draw_camera_rect(
-    camera_query: Query<&Transform, (With<Camera>, Changed<Transform>)>,
-    world_query: Query<&TiledWorldChunking, With<TiledWorldMarker>>,
+    camera_query: Query<(&GlobalTransform, Has<TiledWorldChunkingCamera>), With<Camera>>,
+    world_query: Query<
+        (&TiledWorldChunking, Option<&TiledWorldChunkingMargins>),
+        With<TiledWorldMarker>,
+    >,
     config: Res<TiledDebugWorldChunkConfig>,
     mut gizmos: Gizmos,
 ) {
     let Some(color) = config.world_chunk_color else {
         return;
     };
-    for world_chunking in world_query.iter() {
-        let Some(chunking) = world_chunking.0 else {
+    for (world_chunking, chunking_margins) in world_query.iter() {
+        let Some(ChunkingHalfExtents(chunking)) = world_chunking.0 else {
             continue;
         };
-        for camera_transform in camera_query.iter() {
-            let position = Vec2::new(
-                camera_transform.translation.x,
-                camera_transform.translation.y,
-            );
+        let chunking = chunking_margins
+            .copied()
+            .unwrap_or_default()
+            .spawn_half_extents(chunking);
+        let use_marked_cameras = camera_query.iter().any(|(_, is_marked)| is_marked);
+        for (camera_transform, is_marked) in camera_query.iter() {
+            if use_marked_cameras && !is_marked {
+                continue;
+            }
+            let position = camera_transform.translation().truncate();
             gizmos.rect_2d(Isometry2d::from_translation(position), chunking * 2., color);
         }
     }
@@ -103,8 +111,7 @@ fn draw_maps_rect(
     }
     for (world_handle, world_transform, anchor) in world_query.iter() {
         if let Some(tiled_world) = world_assets.get(world_handle.0.id()) {
-            let offset = tiled_world.offset(anchor);
-            crate::world::for_each_map(tiled_world, world_transform, offset, |idx, aabb| {
+            tiled_world.for_each_map(world_transform, anchor, |idx, aabb| {
                 gizmos.rect_2d(
                     Isometry2d::from_translation(aabb.center()),
                     aabb.half_size() * 2.,
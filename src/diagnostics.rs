@@ -0,0 +1,65 @@
+//! This module contains a [Plugin] reporting maps loading diagnostics.
+//!
+//! Measurements can be read from the [DiagnosticsStore] or displayed using Bevy
+//! [LogDiagnosticsPlugin](bevy::diagnostic::LogDiagnosticsPlugin).
+
+use crate::prelude::*;
+use bevy::{
+    diagnostic::{Diagnostic, DiagnosticPath, Diagnostics, RegisterDiagnostic},
+    prelude::*,
+};
+
+/// `bevy_ecs_tiled` diagnostics exports.
+pub mod prelude {
+    pub use super::TiledDiagnosticsPlugin;
+}
+
+/// `bevy_ecs_tiled` diagnostics [Plugin]
+///
+/// Report, each frame:
+/// - the number of tile entities spawned during this frame, see [TiledDiagnosticsPlugin::TILES_SPAWNED]
+/// - the number of maps whose asset is still loading, see [TiledDiagnosticsPlugin::MAPS_LOADING]
+///
+/// ```rust,no_run
+/// use bevy::{diagnostic::LogDiagnosticsPlugin, prelude::*};
+/// use bevy_ecs_tiled::prelude::*;
+///
+/// App::new()
+///     .add_plugins((TiledDiagnosticsPlugin, LogDiagnosticsPlugin::default()));
+/// ```
+///
+/// [DiagnosticsStore]: bevy::diagnostic::DiagnosticsStore
+#[derive(Default, Copy, Clone, Debug)]
+pub struct TiledDiagnosticsPlugin;
+
+impl TiledDiagnosticsPlugin {
+    /// Number of tile entities spawned during the frame.
+    pub const TILES_SPAWNED: DiagnosticPath = DiagnosticPath::const_new("tiled/tiles_spawned");
+    /// Number of maps whose asset (or one of its dependencies) is still loading.
+    pub const MAPS_LOADING: DiagnosticPath = DiagnosticPath::const_new("tiled/maps_loading");
+}
+
+impl Plugin for TiledDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_diagnostic(Diagnostic::new(Self::TILES_SPAWNED))
+            .register_diagnostic(Diagnostic::new(Self::MAPS_LOADING))
+            .add_systems(PostUpdate, measure);
+    }
+}
+
+fn measure(
+    mut diagnostics: Diagnostics,
+    asset_server: Res<AssetServer>,
+    tile_query: Query<(), Added<TiledMapTile>>,
+    map_query: Query<&TiledMapHandle>,
+) {
+    diagnostics.add_measurement(&TiledDiagnosticsPlugin::TILES_SPAWNED, || {
+        tile_query.iter().count() as f64
+    });
+    diagnostics.add_measurement(&TiledDiagnosticsPlugin::MAPS_LOADING, || {
+        map_query
+            .iter()
+            .filter(|map_handle| !asset_server.is_loaded_with_dependencies(&map_handle.0))
+            .count() as f64
+    });
+}
@@ -0,0 +1,104 @@
+//! This module contains an egui panel showing spawned maps metadata.
+//!
+//! You need to enable the `inspector` feature to use it.
+//!
+//! It complements `bevy_inspector_egui` world inspector, which only shows the raw map
+//! [Component]s, with informations read from the [TiledMap] asset.
+
+use crate::prelude::*;
+use bevy::prelude::*;
+use bevy_inspector_egui::bevy_egui::{egui, EguiContexts, EguiPlugin};
+
+/// `bevy_ecs_tiled` inspector exports.
+pub mod prelude {
+    pub use super::TiledInspectorPlugin;
+}
+
+/// `bevy_ecs_tiled` inspector [Plugin]
+///
+/// Display an egui window listing all spawned maps, along with their size, their tilesets count,
+/// their tiles count and their layers. Layers visibility can be toggled from this window and
+/// maps can be respawned.
+///
+/// ```rust,no_run
+/// use bevy::prelude::*;
+/// use bevy_ecs_tiled::prelude::*;
+///
+/// App::new()
+///     .add_plugins(TiledInspectorPlugin);
+/// ```
+#[derive(Default, Copy, Clone, Debug)]
+pub struct TiledInspectorPlugin;
+
+impl Plugin for TiledInspectorPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<EguiPlugin>() {
+            app.add_plugins(EguiPlugin);
+        }
+        app.add_systems(Update, maps_window);
+    }
+}
+
+fn maps_window(
+    mut commands: Commands,
+    mut contexts: EguiContexts,
+    maps: Res<Assets<TiledMap>>,
+    map_query: Query<
+        (Entity, &TiledMapHandle, &TiledMapStorage, Option<&Name>),
+        With<TiledMapMarker>,
+    >,
+    mut layer_query: Query<(&mut Visibility, Option<&TiledMapLayerStats>), With<TiledMapLayer>>,
+) {
+    egui::Window::new("Tiled maps").show(contexts.ctx_mut(), |ui| {
+        for (map_entity, map_handle, storage, name) in map_query.iter() {
+            let Some(tiled_map) = maps.get(&map_handle.0) else {
+                continue;
+            };
+            let title = name.map_or_else(|| format!("{map_entity}"), |name| name.to_string());
+            egui::CollapsingHeader::new(title)
+                .id_salt(map_entity)
+                .show(ui, |ui| {
+                    ui.label(format!(
+                        "Size: {}x{} tiles ({})",
+                        tiled_map.tilemap_size.x,
+                        tiled_map.tilemap_size.y,
+                        if tiled_map.map.infinite() {
+                            "infinite"
+                        } else {
+                            "finite"
+                        }
+                    ));
+                    ui.label(format!("Tilesets: {}", tiled_map.map.tilesets().len()));
+                    let tile_count: u32 = storage
+                        .layers
+                        .values()
+                        .filter_map(|entity| layer_query.get(*entity).ok())
+                        .filter_map(|(_, stats)| stats.map(|s| s.tile_count))
+                        .sum();
+                    ui.label(format!("Tiles: {tile_count}"));
+
+                    ui.separator();
+                    for (_, layer) in get_all_layers(&tiled_map.map) {
+                        let Some(layer_entity) = storage.layers.get(&layer.id()) else {
+                            continue;
+                        };
+                        let Ok((mut visibility, _)) = layer_query.get_mut(*layer_entity) else {
+                            continue;
+                        };
+                        let mut visible = *visibility != Visibility::Hidden;
+                        if ui.checkbox(&mut visible, layer.name.as_str()).changed() {
+                            *visibility = match visible {
+                                true => Visibility::Inherited,
+                                false => Visibility::Hidden,
+                            };
+                        }
+                    }
+
+                    ui.separator();
+                    if ui.button("Respawn map").clicked() {
+                        commands.entity(map_entity).insert(RespawnTiledMap);
+                    }
+                });
+        }
+    });
+}
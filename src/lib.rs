@@ -16,6 +16,7 @@
 #![deny(missing_debug_implementations)]
 
 pub mod cache;
+pub mod diagnostics;
 pub mod map;
 pub mod names;
 pub mod reader;
@@ -24,20 +25,36 @@ pub mod world;
 #[cfg(feature = "debug")]
 pub mod debug;
 
+#[cfg(feature = "inspector")]
+pub mod inspector;
+
+#[cfg(feature = "navigation")]
+pub mod navigation;
+
 #[cfg(feature = "physics")]
 pub mod physics;
 
 #[cfg(feature = "user_properties")]
 pub mod properties;
 
+#[cfg(test)]
+pub(crate) mod test_utils;
+
 /// `bevy_ecs_tiled` public exports.
 pub mod prelude {
     #[cfg(feature = "debug")]
     pub use super::debug::prelude::*;
+    pub use super::diagnostics::prelude::*;
+    #[cfg(feature = "inspector")]
+    pub use super::inspector::prelude::*;
     pub use super::map::prelude::*;
     pub use super::names::*;
+    #[cfg(feature = "navigation")]
+    pub use super::navigation::prelude::*;
     #[cfg(feature = "physics")]
     pub use super::physics::prelude::*;
+    #[cfg(feature = "user_properties")]
+    pub use super::properties::prelude::*;
     pub use super::world::prelude::*;
     pub use crate::TiledMapPlugin;
     pub use crate::TiledMapPluginConfig;
@@ -90,7 +107,36 @@ impl Plugin for TiledMapPlugin {
         app.insert_resource(cache::TiledResourceCache::new())
             .insert_resource(self.0.clone())
             .register_type::<TiledMapPluginConfig>();
+        app.add_systems(Last, despawn_on_app_exit);
         map::build(app);
         world::build(app);
     }
 }
+
+/// Despawn all maps and worlds when the app is about to exit.
+///
+/// Entities are despawned when this system's [Commands] are applied, at the end
+/// of the [Last] schedule of the frame an [AppExit] event was sent: component
+/// hooks and `OnRemove` observers will run, but systems from later frames will not.
+fn despawn_on_app_exit(
+    mut commands: Commands,
+    mut exit_events: EventReader<AppExit>,
+    world_query: Query<Entity, With<TiledWorldHandle>>,
+    map_query: Query<(Entity, Option<&Parent>), With<TiledMapHandle>>,
+) {
+    if exit_events.is_empty() {
+        return;
+    }
+    exit_events.clear();
+
+    for world_entity in world_query.iter() {
+        commands.entity(world_entity).despawn_recursive();
+    }
+    for (map_entity, parent) in map_query.iter() {
+        // Maps belonging to a world have already been despawned along with their world
+        if parent.is_some_and(|p| world_query.contains(p.get())) {
+            continue;
+        }
+        commands.entity(map_entity).despawn_recursive();
+    }
+}
@@ -2,29 +2,36 @@
 
 #[cfg(feature = "user_properties")]
 use std::ops::Deref;
-use std::{fmt, io::ErrorKind};
+use std::{
+    fmt,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 #[cfg(feature = "user_properties")]
 use bevy::reflect::TypeRegistryArc;
+use serde::{Deserialize, Serialize};
 use tiled::ChunkData;
 
 #[cfg(feature = "user_properties")]
-use crate::properties::load::DeserializedMapProperties;
+use crate::properties::load::{ClassDefaults, DeserializedMapProperties};
 
 use crate::{
-    cache::TiledResourceCache, get_grid_size, get_map_type, iso_projection,
-    reader::BytesResourceReader,
+    cache::TiledResourceCache, for_each_tile, get_all_layers, get_grid_size, get_map_type,
+    iso_projection, reader::BytesResourceReader,
 };
 
 use bevy::{
-    asset::{io::Reader, AssetLoader, AssetPath, LoadContext, LoadedAsset},
+    asset::{io::Reader, AssetLoader, LoadContext, LoadedAsset, ReadAssetBytesError},
+    math::bounding::Aabb2d,
     prelude::*,
-    utils::HashMap,
+    utils::{HashMap, HashSet},
 };
 
 use bevy_ecs_tilemap::prelude::*;
 
-use super::TiledMapAnchor;
+use super::{TiledDrawOrder, TiledMapAnchor, TiledMapLayerStats, TiledMapYFlip};
 
 /// Tiled map [Asset].
 ///
@@ -32,7 +39,10 @@ use super::TiledMapAnchor;
 #[derive(TypePath, Asset)]
 pub struct TiledMap {
     /// The raw Tiled map data
-    pub map: tiled::Map,
+    ///
+    /// It is behind an [Arc] so it can be cheaply cloned and kept around, for instance to
+    /// inspect map objects or properties from another thread.
+    pub map: Arc<tiled::Map>,
     /// Map size in tiles
     pub tilemap_size: TilemapSize,
     /// Map bounding box, unanchored.
@@ -59,6 +69,19 @@ pub struct TiledMap {
     ///
     /// Key is the Tiled tileset index
     pub(crate) tilesets: HashMap<usize, TiledMapTileset>,
+    /// Index of the map tilesets, using their name as key
+    ///
+    /// Value is the Tiled tileset index.
+    /// If several tilesets share the same name, only the first one is referenced.
+    pub(crate) tilesets_by_name: HashMap<String, usize>,
+    /// Tiled IDs of the object layers whose objects are drawn in the order they appear
+    ///
+    /// Other object layers use [TiledDrawOrder::TopDown].
+    pub(crate) index_draw_order_layers: HashSet<u32>,
+    /// Statistics of each tiles layer, computed once when loading the map
+    ///
+    /// Key is the layer ID. Bounding boxes use inverted Y coordinates, ie. [TiledMapYFlip] `(true)`.
+    pub(crate) layer_stats: HashMap<usize, TiledMapLayerStats>,
     /// Map properties
     #[cfg(feature = "user_properties")]
     pub(crate) properties: DeserializedMapProperties,
@@ -87,6 +110,228 @@ impl TiledMap {
 
         offset
     }
+
+    /// Retrieve a tileset and its Tiled index using the tileset name.
+    ///
+    /// If several tilesets share the same name, returns the first one.
+    pub fn get_tileset_by_name(&self, name: &str) -> Option<(usize, &tiled::Tileset)> {
+        self.tilesets_by_name.get(name).and_then(|index| {
+            self.map
+                .tilesets()
+                .get(*index)
+                .map(|t| (*index, t.as_ref()))
+        })
+    }
+
+    /// Retrieve the drawing order of an object layer using its Tiled ID.
+    pub fn get_object_layer_draw_order(&self, layer_id: u32) -> TiledDrawOrder {
+        if self.index_draw_order_layers.contains(&layer_id) {
+            TiledDrawOrder::Index
+        } else {
+            TiledDrawOrder::TopDown
+        }
+    }
+
+    /// Retrieve the IDs of the tiles of a tileset which are actually used in the map, using the tileset Tiled index.
+    ///
+    /// It includes tiles from tiles layers, tiles objects and animation frames of these tiles.
+    /// Returns [None] if the tileset could not be loaded.
+    pub fn get_used_tile_ids(&self, index: usize) -> Option<&HashSet<tiled::TileId>> {
+        self.tilesets.get(&index).map(|t| &t.used_tile_ids)
+    }
+
+    /// Number of layers in this map, including group layers and the layers they contain.
+    ///
+    /// Layers IDs (as in [TiledLayerCreated::id](crate::prelude::TiledLayerCreated::id)) range from `0` to `layer_count() - 1`.
+    pub fn layer_count(&self) -> usize {
+        get_all_layers(&self.map).len()
+    }
+
+    /// Iterate over the names of all the layers in this map, ordered by layer ID.
+    ///
+    /// Group layers come right before the layers they contain.
+    pub fn layer_names(&self) -> impl Iterator<Item = String> + '_ {
+        get_all_layers(&self.map)
+            .into_iter()
+            .map(|(_, layer)| layer.name.clone())
+    }
+
+    /// Retrieve a layer using its name, including layers nested in group layers.
+    ///
+    /// If several layers share the same name, the first one is returned.
+    /// Unlike [find_layer](crate::prelude::find_layer), it does not require the map to be spawned.
+    pub fn layer_by_name(&self, name: &str) -> Option<tiled::Layer<'_>> {
+        get_all_layers(&self.map)
+            .into_iter()
+            .find(|(_, layer)| layer.name == name)
+            .map(|(_, layer)| layer)
+    }
+
+    /// Compute the world-space bounding box of the whole map.
+    ///
+    /// `map_transform` and `anchor` are the [GlobalTransform] and [TiledMapAnchor] of the map [Entity].
+    /// Layers offset are not taken into account.
+    pub fn map_aabb(&self, map_transform: &GlobalTransform, anchor: &TiledMapAnchor) -> Aabb2d {
+        let offset = self.anchor_offset(anchor);
+        let (min, max) = [
+            self.rect.min,
+            Vec2::new(self.rect.max.x, self.rect.min.y),
+            Vec2::new(self.rect.min.x, self.rect.max.y),
+            self.rect.max,
+        ]
+        .into_iter()
+        .map(|corner| {
+            map_transform
+                .transform_point((corner + offset).extend(0.))
+                .truncate()
+        })
+        .fold((Vec2::MAX, Vec2::MIN), |(min, max), p| {
+            (min.min(p), max.max(p))
+        });
+        Aabb2d { min, max }
+    }
+
+    /// Retrieve the texture index of the tile at given position in a tiles layer.
+    ///
+    /// The lookup only uses the map data, not the spawned entities, so it can be used before the
+    /// map is spawned. If several layers share the same name, the first one is used.
+    /// Returned index is the one used for the [TileTextureIndex] of the tile entity.
+    /// `y_flip` is the [TiledMapYFlip] of the map [Entity], so `pos` matches the tile entity [TilePos]
+    /// (note it does not take a [TiledMapTypeOverride](crate::prelude::TiledMapTypeOverride) into account).
+    /// Returns [None] if there is no such tiles layer or no tile at this position.
+    pub fn tile_at(&self, layer_name: &str, pos: TilePos, y_flip: &TiledMapYFlip) -> Option<u32> {
+        let tiles_layer = get_all_layers(&self.map)
+            .into_iter()
+            .filter(|(_, layer)| layer.name == layer_name)
+            .find_map(|(_, layer)| layer.as_tile_layer())?;
+        if pos.x >= self.tilemap_size.x || pos.y >= self.tilemap_size.y {
+            return None;
+        }
+        // Convert to Tiled coordinates: infinite maps are shifted so their top-left chunk is at (0, 0)
+        let pos = y_flip.convert(pos, &get_map_type(&self.map), &self.tilemap_size);
+        let mut x = pos.x as i32;
+        let mut y = (self.tilemap_size.y - 1 - pos.y) as i32;
+        if let tiled::TileLayer::Infinite(_) = tiles_layer {
+            x += self.topleft_chunk.0 * ChunkData::WIDTH as i32;
+            y += self.topleft_chunk.1 * ChunkData::HEIGHT as i32;
+        }
+        let layer_tile = tiles_layer.get_tile(x, y)?;
+        self.tilesets
+            .get(&layer_tile.tileset_index())?
+            .texture_index(layer_tile.id())
+    }
+
+    /// Offset between the map origin when using given [TiledMapAnchor] and [TiledMapAnchor::BottomLeft].
+    fn anchor_offset(&self, anchor: &TiledMapAnchor) -> Vec2 {
+        (self.offset(anchor) - self.offset(&TiledMapAnchor::BottomLeft)).truncate()
+    }
+
+    /// Returns `true` if at least one tiles layer of this map contains a tile.
+    ///
+    /// Group layers are browsed recursively.
+    pub fn has_content(&self) -> bool {
+        tiles_layers(&self.map)
+            .iter()
+            .any(|(_, tiles_layer)| any_tile(tiles_layer, |_| true))
+    }
+
+    /// Returns `true` if at least one tile with collision objects is used in a tiles layer
+    /// whose name is in `layer_names`.
+    ///
+    /// Can be used to skip physics processing for decoration-only maps.
+    pub fn has_physics_content(&self, layer_names: &[&str]) -> bool {
+        tiles_layers(&self.map)
+            .iter()
+            .filter(|(layer, _)| layer_names.contains(&layer.name.as_str()))
+            .any(|(_, tiles_layer)| {
+                any_tile(tiles_layer, |layer_tile| {
+                    layer_tile
+                        .get_tile()
+                        .is_some_and(|tile| tile.collision.is_some())
+                })
+            })
+    }
+}
+
+/// Compute statistics about the tiles of a tiles layer.
+///
+/// Bounding box uses inverted Y coordinates, ie. the [TilePos] of tiles with [TiledMapYFlip] `(true)`.
+fn compute_layer_stats(tiled_map: &TiledMap, tiles_layer: &tiled::TileLayer) -> TiledMapLayerStats {
+    let mut tile_count = 0;
+    let mut unique_tile_ids = HashSet::default();
+    let mut bounding_rect: Option<Rect> = None;
+    for_each_tile(tiled_map, tiles_layer, |layer_tile, _, tile_pos, _| {
+        tile_count += 1;
+        unique_tile_ids.insert((layer_tile.tileset_index(), layer_tile.id()));
+        let pos = Vec2::new(tile_pos.x as f32, tile_pos.y as f32);
+        bounding_rect = Some(match bounding_rect {
+            Some(rect) => rect.union_point(pos),
+            None => Rect::from_corners(pos, pos),
+        });
+    });
+    TiledMapLayerStats {
+        tile_count,
+        unique_tile_ids: unique_tile_ids.len() as u32,
+        bounding_rect,
+    }
+}
+
+/// All tiles layers of a map, including the ones nested in group layers.
+fn tiles_layers(map: &tiled::Map) -> Vec<(tiled::Layer<'_>, tiled::TileLayer<'_>)> {
+    let mut out = vec![];
+    let mut to_process = Vec::from_iter(map.layers());
+    while let Some(layer) = to_process.pop() {
+        match layer.layer_type() {
+            tiled::LayerType::Group(group) => to_process.extend(group.layers()),
+            tiled::LayerType::Tiles(tiles_layer) => out.push((layer, tiles_layer)),
+            _ => {}
+        }
+    }
+    out
+}
+
+/// Returns `true` if any tile of the layer matches the given predicate.
+fn any_tile(
+    tiles_layer: &tiled::TileLayer<'_>,
+    mut f: impl FnMut(tiled::LayerTile<'_>) -> bool,
+) -> bool {
+    match tiles_layer {
+        tiled::TileLayer::Finite(layer) => (0..layer.width() as i32)
+            .any(|x| (0..layer.height() as i32).any(|y| layer.get_tile(x, y).is_some_and(&mut f))),
+        tiled::TileLayer::Infinite(layer) => layer.chunks().any(|(_, chunk)| {
+            (0..ChunkData::WIDTH as i32).any(|x| {
+                (0..ChunkData::HEIGHT as i32).any(|y| chunk.get_tile(x, y).is_some_and(&mut f))
+            })
+        }),
+    }
+}
+
+/// Warn about tiles layers using a tileset which cannot be used to render them.
+///
+/// This happens when a tileset failed to load, is an images collection tileset with the
+/// `atlas` feature or contains images of different sizes: these tiles will not be displayed.
+fn warn_unusable_tilesets(map: &tiled::Map, tilesets: &HashMap<usize, TiledMapTileset>) {
+    for (layer, tiles_layer) in tiles_layers(map) {
+        let mut unusable = std::collections::BTreeSet::new();
+        any_tile(&tiles_layer, |layer_tile| {
+            let index = layer_tile.tileset_index();
+            if !tilesets
+                .get(&index)
+                .is_some_and(|t| t.usable_for_tiles_layer)
+            {
+                unusable.insert(index);
+            }
+            false
+        });
+        for index in unusable {
+            warn!(
+                "Tiles from layer '{}' using tileset '{}' (index {}) will not be displayed: tileset failed to load or cannot be used for tiles layers",
+                layer.name,
+                map.tilesets().get(index).map_or("<unknown>", |t| t.name.as_str()),
+                index
+            );
+        }
+    }
 }
 
 impl fmt::Debug for TiledMap {
@@ -116,6 +361,65 @@ pub(crate) struct TiledMapTileset {
     /// The offset into the tileset_images for each tile id within each tileset.
     #[cfg(not(feature = "atlas"))]
     pub(crate) tile_image_offsets: HashMap<tiled::TileId, u32>,
+    /// IDs of the tiles of this tileset which are actually used in the map
+    ///
+    /// It includes tiles from tiles layers, tiles objects and animation frames of these tiles.
+    pub(crate) used_tile_ids: HashSet<tiled::TileId>,
+}
+
+impl TiledMapTileset {
+    /// Index of the given tile in the tileset texture, as used by [TileTextureIndex].
+    pub(crate) fn texture_index(&self, tile_id: tiled::TileId) -> Option<u32> {
+        match &self.tilemap_texture {
+            TilemapTexture::Single(_) => Some(tile_id),
+            #[cfg(not(feature = "atlas"))]
+            TilemapTexture::Vector(_) => self.tile_image_offsets.get(&tile_id).copied(),
+            #[cfg(not(feature = "atlas"))]
+            _ => unreachable!(),
+        }
+    }
+}
+
+/// [TiledMapLoader] settings.
+///
+/// Can be provided when loading a map using [AssetServer::load_with_settings].
+#[derive(Serialize, Deserialize, Clone, Debug)]
+// Not Copy since `class_definitions_path` is not Copy and only exists with the `user_properties`
+// feature: enabling a feature must not break downstream code
+#[allow(missing_copy_implementations)]
+pub struct TiledMapLoaderSettings {
+    /// Do not keep data for tiles which are not used in the map.
+    ///
+    /// When enabled, images from images collection tilesets and tiles custom properties
+    /// will only be loaded for tiles which are actually used in the map.
+    /// Note that raw `tiled` data from [TiledMap::map] is not affected.
+    /// With the `atlas` feature and without the `user_properties` feature, there is nothing to
+    /// strip: a warning is emitted and this setting is ignored.
+    pub strip_unused_tiles: bool,
+    /// Maximum size, in tiles, of an infinite map along each axis.
+    ///
+    /// If an infinite map exceeds this size, a warning will be emitted when loading it.
+    /// The map will still be loaded as-is.
+    pub infinite_map_size_warn_threshold: u32,
+    /// Path to a Tiled property types JSON file, relative to the assets folder.
+    ///
+    /// Tiled does not save class members which still have their default value:
+    /// when provided, defaults from this file are used to fill them.
+    /// The file can be exported from Tiled custom types editor or be the one exported by
+    /// [TiledMapPluginConfig::tiled_types_export_file](crate::TiledMapPluginConfig::tiled_types_export_file).
+    #[cfg(feature = "user_properties")]
+    pub class_definitions_path: Option<PathBuf>,
+}
+
+impl Default for TiledMapLoaderSettings {
+    fn default() -> Self {
+        Self {
+            strip_unused_tiles: false,
+            infinite_map_size_warn_threshold: 2048,
+            #[cfg(feature = "user_properties")]
+            class_definitions_path: None,
+        }
+    }
 }
 
 pub(crate) struct TiledMapLoader {
@@ -140,17 +444,186 @@ pub enum TiledMapLoaderError {
     /// An [IO](std::io) Error
     #[error("Could not load Tiled file: {0}")]
     Io(#[from] std::io::Error),
+    /// A tileset image could not be loaded
+    #[error("Could not load tileset image: {0}")]
+    Image(#[from] ReadAssetBytesError),
+    /// Map layers data use a compression which is not supported
+    ///
+    /// Supported compressions are `gzip`, `zlib` and `zstd`.
+    #[error(
+        "Unsupported layer data compression '{0}' (supported compressions are gzip, zlib and zstd)"
+    )]
+    UnsupportedCompression(String),
+}
+
+/// Layer data compressions supported by the `tiled` crate.
+const SUPPORTED_COMPRESSIONS: &[&str] = &["gzip", "zlib", "zstd"];
+
+/// Look for a layer data compression which is not supported in the raw `.tmx` file.
+///
+/// The `tiled` crate only reports a generic error in this case, so we check it beforehand
+/// to give a more helpful error.
+fn find_unsupported_compression(bytes: &[u8]) -> Option<String> {
+    const ATTRIBUTE: &[u8] = b"compression=\"";
+    let mut remaining = bytes;
+    while let Some(start) = remaining
+        .windows(ATTRIBUTE.len())
+        .position(|w| w == ATTRIBUTE)
+    {
+        remaining = &remaining[start + ATTRIBUTE.len()..];
+        let end = remaining.iter().position(|b| *b == b'"')?;
+        let compression = String::from_utf8_lossy(&remaining[..end]);
+        if !compression.is_empty() && !SUPPORTED_COMPRESSIONS.contains(&compression.as_ref()) {
+            return Some(compression.into_owned());
+        }
+        remaining = &remaining[end..];
+    }
+    None
+}
+
+/// Look for object layers using the `index` drawing order in the raw `.tmx` file.
+///
+/// The `tiled` crate does not expose object layers `draworder` attribute, so we read it from
+/// `<objectgroup>` tags. Returns the Tiled IDs of these layers.
+fn find_index_draw_order_layers(bytes: &[u8]) -> HashSet<u32> {
+    const TAG: &[u8] = b"<objectgroup";
+    // Attribute value, if it is set on this tag
+    fn attribute<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+        let pattern = format!("{name}=\"");
+        tag.match_indices(&pattern)
+            .find(|(start, _)| tag[..*start].ends_with(char::is_whitespace))
+            .and_then(|(start, _)| {
+                let value = &tag[start + pattern.len()..];
+                value.find('"').map(|end| &value[..end])
+            })
+    }
+
+    let mut layers = HashSet::default();
+    let mut remaining = bytes;
+    while let Some(start) = remaining.windows(TAG.len()).position(|w| w == TAG) {
+        remaining = &remaining[start + TAG.len()..];
+        let Some(end) = remaining.iter().position(|b| *b == b'>') else {
+            break;
+        };
+        let tag = String::from_utf8_lossy(&remaining[..end]);
+        if attribute(&tag, "draworder") == Some("index") {
+            if let Some(id) = attribute(&tag, "id").and_then(|id| id.parse().ok()) {
+                layers.insert(id);
+            }
+        }
+        remaining = &remaining[end..];
+    }
+    layers
+}
+
+/// Load a tileset image and return its [Handle].
+///
+/// If several tilesets share the same source image, they will share the same [Handle].
+/// Image file is read first to make sure it exists: on error, the caller should skip the
+/// tileset rather than failing the whole map, layers using it are then skipped when
+/// spawning the map.
+async fn load_tileset_image(
+    load_context: &mut LoadContext<'_>,
+    images: &mut HashMap<PathBuf, Handle<Image>>,
+    source: &Path,
+) -> Result<Handle<Image>, TiledMapLoaderError> {
+    if let Some(handle) = images.get(source) {
+        return Ok(handle.clone());
+    }
+    trace!("Loading tileset image from {source:?}");
+    load_context.read_asset_bytes(source.to_path_buf()).await?;
+    let handle: Handle<Image> = load_context.load(source.to_path_buf());
+    images.insert(source.to_path_buf(), handle.clone());
+    Ok(handle)
+}
+
+/// Compute IDs of the tiles which are actually used in the map, for each tileset.
+///
+/// It includes tiles from tiles layers, tiles objects and animation frames of these tiles.
+fn compute_used_tile_ids(map: &tiled::Map) -> HashMap<usize, HashSet<tiled::TileId>> {
+    use tiled::{LayerType, TileLayer};
+
+    let mut used_tile_ids: HashMap<usize, HashSet<tiled::TileId>> = HashMap::default();
+    let mut to_process = Vec::from_iter(map.layers());
+    while let Some(layer) = to_process.pop() {
+        match layer.layer_type() {
+            LayerType::Tiles(TileLayer::Finite(layer)) => {
+                for x in 0..layer.width() as i32 {
+                    for y in 0..layer.height() as i32 {
+                        if let Some(tile) = layer.get_tile_data(x, y) {
+                            used_tile_ids
+                                .entry(tile.tileset_index())
+                                .or_default()
+                                .insert(tile.id());
+                        }
+                    }
+                }
+            }
+            LayerType::Tiles(TileLayer::Infinite(layer)) => {
+                for (_, chunk) in layer.chunks() {
+                    for x in 0..ChunkData::WIDTH as i32 {
+                        for y in 0..ChunkData::HEIGHT as i32 {
+                            if let Some(tile) = chunk.get_tile_data(x, y) {
+                                used_tile_ids
+                                    .entry(tile.tileset_index())
+                                    .or_default()
+                                    .insert(tile.id());
+                            }
+                        }
+                    }
+                }
+            }
+            LayerType::Objects(layer) => {
+                for object in layer.object_data() {
+                    if let Some(tile) = object.tile_data() {
+                        // Also include tiles from templates, using the matching map tileset
+                        if let Some(tileset_index) =
+                            super::utils::get_tileset_index(map, tile.tileset_location())
+                        {
+                            used_tile_ids
+                                .entry(tileset_index)
+                                .or_default()
+                                .insert(tile.id());
+                        }
+                    }
+                }
+            }
+            LayerType::Group(group) => {
+                to_process.extend(group.layers());
+            }
+            LayerType::Image(_) => {}
+        }
+    }
+
+    // Also include animation frames of used tiles
+    for (tileset_index, tile_ids) in used_tile_ids.iter_mut() {
+        let Some(tileset) = map.tilesets().get(*tileset_index) else {
+            continue;
+        };
+        let frames: Vec<tiled::TileId> = tile_ids
+            .iter()
+            .filter_map(|id| {
+                tileset
+                    .get_tile(*id)
+                    .and_then(|tile| tile.animation.clone())
+            })
+            .flat_map(|animation| animation.into_iter().map(|frame| frame.tile_id))
+            .collect();
+        tile_ids.extend(frames);
+    }
+
+    used_tile_ids
 }
 
 impl AssetLoader for TiledMapLoader {
     type Asset = TiledMap;
-    type Settings = ();
+    type Settings = TiledMapLoaderSettings;
     type Error = TiledMapLoaderError;
 
     async fn load(
         &self,
         reader: &mut dyn Reader,
-        _settings: &Self::Settings,
+        settings: &Self::Settings,
         load_context: &mut LoadContext<'_>,
     ) -> Result<Self::Asset, Self::Error> {
         let mut bytes = Vec::new();
@@ -158,6 +631,11 @@ impl AssetLoader for TiledMapLoader {
 
         debug!("Start loading map '{}'", load_context.path().display());
 
+        if let Some(compression) = find_unsupported_compression(&bytes) {
+            return Err(TiledMapLoaderError::UnsupportedCompression(compression));
+        }
+        let index_draw_order_layers = find_index_draw_order_layers(&bytes);
+
         let map_path = load_context.path().to_path_buf();
         let map = {
             // Allow the loader to also load tileset images.
@@ -171,7 +649,20 @@ impl AssetLoader for TiledMapLoader {
             })?
         };
 
+        let used_tile_ids = compute_used_tile_ids(&map);
+        // Image collection tilesets are not supported with the `atlas` feature: only properties
+        // of unused tiles can be stripped
+        #[cfg(all(feature = "atlas", not(feature = "user_properties")))]
+        if settings.strip_unused_tiles {
+            warn!(
+                "Map '{}' is loaded with `strip_unused_tiles` but it has no effect when using the `atlas` feature without the `user_properties` feature",
+                map_path.display()
+            );
+        }
+        #[cfg(any(not(feature = "atlas"), feature = "user_properties"))]
+        let stripped_tile_ids = settings.strip_unused_tiles.then_some(&used_tile_ids);
         let mut tilesets = HashMap::default();
+        let mut images = HashMap::default();
         for (tileset_index, tileset) in map.tilesets().iter().enumerate() {
             debug!(
                 "Loading tileset (index={:?} name={:?}) from {:?}",
@@ -193,11 +684,30 @@ impl AssetLoader for TiledMapLoader {
                         let mut usable_for_tiles_layer = true;
                         let mut image_size: Option<(i32, i32)> = None;
                         let mut tile_images: Vec<Handle<Image>> = Vec::new();
+                        let mut image_error = None;
                         for (tile_id, tile) in tileset.tiles() {
+                            if stripped_tile_ids.is_some_and(|used| {
+                                !used
+                                    .get(&tileset_index)
+                                    .is_some_and(|ids| ids.contains(&tile_id))
+                            }) {
+                                continue;
+                            }
                             if let Some(img) = &tile.image {
-                                let asset_path = AssetPath::from(img.source.clone());
-                                trace!("Loading tile image from {asset_path:?} as image ({tileset_index}, {tile_id})");
-                                let texture: Handle<Image> = load_context.load(asset_path.clone());
+                                trace!("Loading tile image from {:?} as image ({tileset_index}, {tile_id})", img.source);
+                                let texture = match load_tileset_image(
+                                    load_context,
+                                    &mut images,
+                                    &img.source,
+                                )
+                                .await
+                                {
+                                    Ok(texture) => texture,
+                                    Err(e) => {
+                                        image_error = Some(e);
+                                        break;
+                                    }
+                                };
                                 tile_image_offsets.insert(tile_id, tile_images.len() as u32);
                                 tile_images.push(texture.clone());
                                 if usable_for_tiles_layer {
@@ -211,6 +721,13 @@ impl AssetLoader for TiledMapLoader {
                                 }
                             }
                         }
+                        if let Some(e) = image_error {
+                            warn!(
+                                "Skipping tileset '{}' (index={tileset_index}): {e}",
+                                tileset.name
+                            );
+                            continue;
+                        }
                         if !usable_for_tiles_layer {
                             debug!(
                                 "Tileset (index={:?}) have non constant image size and cannot be used for tiles layer",
@@ -221,8 +738,17 @@ impl AssetLoader for TiledMapLoader {
                     }
                 }
                 Some(img) => {
-                    let asset_path = AssetPath::from(img.source.clone());
-                    let texture: Handle<Image> = load_context.load(asset_path.clone());
+                    let texture =
+                        match load_tileset_image(load_context, &mut images, &img.source).await {
+                            Ok(texture) => texture,
+                            Err(e) => {
+                                warn!(
+                                    "Skipping tileset '{}' (index={tileset_index}): {e}",
+                                    tileset.name
+                                );
+                                continue;
+                            }
+                        };
 
                     let columns = (img.width as u32 - tileset.margin + tileset.spacing)
                         / (tileset.tile_width + tileset.spacing);
@@ -232,10 +758,7 @@ impl AssetLoader for TiledMapLoader {
                             columns,
                             tileset.tilecount / columns,
                             Some(UVec2::new(tileset.spacing, tileset.spacing)),
-                            Some(UVec2::new(
-                                tileset.offset_x as u32 + tileset.margin,
-                                tileset.offset_y as u32 + tileset.margin,
-                            )),
+                            Some(UVec2::new(tileset.margin, tileset.margin)),
                         );
                         texture_atlas_layout_handle = Some(load_context.add_loaded_labeled_asset(
                             tileset.name.clone(),
@@ -254,12 +777,21 @@ impl AssetLoader for TiledMapLoader {
                     texture_atlas_layout_handle,
                     #[cfg(not(feature = "atlas"))]
                     tile_image_offsets,
+                    used_tile_ids: used_tile_ids
+                        .get(&tileset_index)
+                        .cloned()
+                        .unwrap_or_default(),
                 },
             );
         }
 
+        warn_unusable_tilesets(&map, &tilesets);
+
         let mut infinite = false;
 
+        // Note that chunks size is always ChunkData::WIDTH x ChunkData::HEIGHT, whatever
+        // the chunk size configured in Tiled: the `tiled` crate re-splits chunks
+        // read from the .tmx file into fixed size chunks when parsing infinite layers.
         // Determine top left chunk index of all infinite layers for this map
         let mut topleft = (999999, 999999);
         for layer in map.layers() {
@@ -281,41 +813,49 @@ impl AssetLoader for TiledMapLoader {
             }
         }
 
-        let map_type = get_map_type(&map);
         let grid_size = get_grid_size(&map);
         let (tilemap_size, tiled_offset) = if infinite {
             debug!(
                 "(infinite map) topleft = {:?}, bottomright = {:?}",
                 topleft, bottomright
             );
+            let tilemap_size = TilemapSize {
+                x: (bottomright.0 - topleft.0 + 1) as u32 * ChunkData::WIDTH,
+                y: (bottomright.1 - topleft.1 + 1) as u32 * ChunkData::HEIGHT,
+            };
+            let threshold = settings.infinite_map_size_warn_threshold;
+            if tilemap_size.x > threshold || tilemap_size.y > threshold {
+                warn!(
+                    "Infinite map {:?} is very large ({}x{} tiles, threshold = {}): \
+                    it may use a lot of memory, consider splitting it into several maps using a Tiled world",
+                    map_path, tilemap_size.x, tilemap_size.y, threshold
+                );
+            }
             (
-                TilemapSize {
-                    x: (bottomright.0 - topleft.0 + 1) as u32 * ChunkData::WIDTH,
-                    y: (bottomright.1 - topleft.1 + 1) as u32 * ChunkData::HEIGHT,
-                },
-                match map_type {
-                    TilemapType::Square => Vec2 {
+                tilemap_size,
+                match (&map.orientation, &map.stagger_axis) {
+                    (tiled::Orientation::Orthogonal, _) => Vec2 {
                         x: -topleft.0 as f32 * ChunkData::WIDTH as f32 * grid_size.x,
                         y: topleft.1 as f32 * ChunkData::HEIGHT as f32 * grid_size.y,
                     },
-                    TilemapType::Hexagon(HexCoordSystem::ColumnOdd)
-                    | TilemapType::Hexagon(HexCoordSystem::ColumnEven) => Vec2 {
+                    (
+                        tiled::Orientation::Hexagonal | tiled::Orientation::Staggered,
+                        tiled::StaggerAxis::X,
+                    ) => Vec2 {
                         x: -topleft.0 as f32 * ChunkData::WIDTH as f32 * grid_size.x * 0.75,
                         y: topleft.1 as f32 * ChunkData::HEIGHT as f32 * grid_size.y,
                     },
-                    TilemapType::Hexagon(HexCoordSystem::RowOdd)
-                    | TilemapType::Hexagon(HexCoordSystem::RowEven) => Vec2 {
+                    (
+                        tiled::Orientation::Hexagonal | tiled::Orientation::Staggered,
+                        tiled::StaggerAxis::Y,
+                    ) => Vec2 {
                         x: -topleft.0 as f32 * ChunkData::WIDTH as f32 * grid_size.x,
                         y: topleft.1 as f32 * ChunkData::HEIGHT as f32 * grid_size.y * 0.75,
                     },
-                    TilemapType::Isometric(IsoCoordSystem::Diamond) => Vec2 {
+                    (tiled::Orientation::Isometric, _) => Vec2 {
                         x: -topleft.0 as f32 * ChunkData::WIDTH as f32 * grid_size.y,
                         y: -topleft.1 as f32 * ChunkData::HEIGHT as f32 * grid_size.y,
                     },
-                    TilemapType::Isometric(IsoCoordSystem::Staggered) => {
-                        panic!("Isometric (Staggered) map is not supported");
-                    }
-                    _ => unreachable!(),
                 },
             )
         } else {
@@ -332,22 +872,26 @@ impl AssetLoader for TiledMapLoader {
 
         let rect = Rect {
             min: Vec2::ZERO,
-            max: match map_type {
-                TilemapType::Square => Vec2 {
+            max: match (&map.orientation, &map.stagger_axis) {
+                (tiled::Orientation::Orthogonal, _) => Vec2 {
                     x: tilemap_size.x as f32 * grid_size.x,
                     y: tilemap_size.y as f32 * grid_size.y,
                 },
-                TilemapType::Hexagon(HexCoordSystem::ColumnOdd)
-                | TilemapType::Hexagon(HexCoordSystem::ColumnEven) => Vec2 {
+                (
+                    tiled::Orientation::Hexagonal | tiled::Orientation::Staggered,
+                    tiled::StaggerAxis::X,
+                ) => Vec2 {
                     x: tilemap_size.x as f32 * grid_size.x * 0.75,
                     y: tilemap_size.y as f32 * grid_size.y,
                 },
-                TilemapType::Hexagon(HexCoordSystem::RowOdd)
-                | TilemapType::Hexagon(HexCoordSystem::RowEven) => Vec2 {
+                (
+                    tiled::Orientation::Hexagonal | tiled::Orientation::Staggered,
+                    tiled::StaggerAxis::Y,
+                ) => Vec2 {
                     x: tilemap_size.x as f32 * grid_size.x,
                     y: tilemap_size.y as f32 * grid_size.y * 0.75,
                 },
-                TilemapType::Isometric(IsoCoordSystem::Diamond) => {
+                (tiled::Orientation::Isometric, _) => {
                     let topleft = iso_projection(Vec2::ZERO, &tilemap_size, &grid_size);
                     let topright = iso_projection(
                         Vec2 {
@@ -360,32 +904,70 @@ impl AssetLoader for TiledMapLoader {
 
                     2. * (topright - topleft)
                 }
-                TilemapType::Isometric(IsoCoordSystem::Staggered) => {
-                    panic!("Isometric (Staggered) map is not supported");
-                }
-                _ => unreachable!(),
             },
         };
 
         #[cfg(feature = "user_properties")]
-        let properties =
-            DeserializedMapProperties::load(&map, self.registry.read().deref(), load_context);
+        let class_defaults = match &settings.class_definitions_path {
+            Some(path) => {
+                let bytes = load_context
+                    .read_asset_bytes(path.clone())
+                    .await
+                    .map_err(|e| std::io::Error::new(ErrorKind::NotFound, e))?;
+                Some(ClassDefaults::from_json(&bytes).map_err(|e| {
+                    std::io::Error::new(
+                        ErrorKind::InvalidData,
+                        format!("Could not parse class definitions {path:?}: {e}"),
+                    )
+                })?)
+            }
+            None => None,
+        };
+
+        #[cfg(feature = "user_properties")]
+        let properties = DeserializedMapProperties::load(
+            &map,
+            self.registry.read().deref(),
+            load_context,
+            stripped_tile_ids,
+            class_defaults.as_ref(),
+        );
 
         #[cfg(feature = "user_properties")]
         trace!(?properties, "user properties");
         trace!(?tilesets, "tilesets");
 
-        let asset_map = TiledMap {
-            map,
+        let mut tilesets_by_name = HashMap::default();
+        for (tileset_index, tileset) in map.tilesets().iter().enumerate() {
+            tilesets_by_name
+                .entry(tileset.name.clone())
+                .or_insert(tileset_index);
+        }
+
+        let mut asset_map = TiledMap {
+            map: Arc::new(map),
             tilemap_size,
             tiled_offset,
             rect,
             topleft_chunk: topleft,
             bottomright_chunk: bottomright,
             tilesets,
+            tilesets_by_name,
+            index_draw_order_layers,
+            layer_stats: HashMap::default(),
             #[cfg(feature = "user_properties")]
             properties,
         };
+        let layer_stats = get_all_layers(&asset_map.map)
+            .into_iter()
+            .enumerate()
+            .filter_map(|(layer_id, (_, layer))| {
+                layer
+                    .as_tile_layer()
+                    .map(|tiles_layer| (layer_id, compute_layer_stats(&asset_map, &tiles_layer)))
+            })
+            .collect();
+        asset_map.layer_stats = layer_stats;
         debug!(
             "Loaded map '{}': {:?}",
             load_context.path().display(),
@@ -399,3 +981,220 @@ impl AssetLoader for TiledMapLoader {
         EXTENSIONS
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::load_map;
+
+    #[test]
+    fn unsupported_compression() {
+        assert_eq!(
+            find_unsupported_compression(br#"<data encoding="base64" compression="zstd">"#),
+            None
+        );
+        assert_eq!(
+            find_unsupported_compression(br#"<data encoding="csv">"#),
+            None
+        );
+        assert_eq!(
+            find_unsupported_compression(
+                br#"<data encoding="base64" compression="gzip"></data><data encoding="base64" compression="lz4">"#
+            ),
+            Some(String::from("lz4"))
+        );
+    }
+
+    #[test]
+    fn missing_tileset_image_skips_tileset() {
+        let (app, handle) = load_map("maps/orthogonal/missing_image.tmx");
+        let tiled_map = app
+            .world()
+            .resource::<Assets<TiledMap>>()
+            .get(&handle)
+            .expect("map should be loaded despite its missing tileset image");
+        assert!(!tiled_map.tilesets.contains_key(&0));
+        assert!(tiled_map.tilesets.contains_key(&1));
+    }
+
+    #[test]
+    fn index_draw_order_layers() {
+        let layers = find_index_draw_order_layers(
+            br#"<objectgroup id="2" name="a" draworder="index">
+<object id="1"/></objectgroup>
+<objectgroup draworder="topdown" id="3" name="b"/>
+<objectgroup name="c" id="4"/>
+<objectgroup
+ id="5" draworder="index"/>"#,
+        );
+        assert_eq!(layers, HashSet::from_iter([2, 5]));
+    }
+
+    #[test]
+    #[cfg(not(feature = "atlas"))]
+    fn inline_tilesets() {
+        for path in [
+            "maps/orthogonal/finite_embedded.tmx",
+            "maps/orthogonal/infinite_embedded.tmx",
+        ] {
+            let (app, handle) = load_map(path);
+            let tiled_map = app
+                .world()
+                .resource::<Assets<TiledMap>>()
+                .get(&handle)
+                .unwrap_or_else(|| panic!("map {path} should be loaded"));
+
+            assert_eq!(tiled_map.tilesets.len(), tiled_map.map.tilesets().len());
+            let images = app.world().resource::<Assets<Image>>();
+            for tileset in tiled_map.tilesets.values() {
+                assert!(tileset.usable_for_tiles_layer);
+                let TilemapTexture::Vector(tile_images) = &tileset.tilemap_texture else {
+                    panic!("inline image collection tileset should use a vector of images");
+                };
+                assert!(!tile_images.is_empty());
+                assert!(tile_images.iter().all(|image| images.contains(image)));
+            }
+        }
+    }
+
+    #[test]
+    fn object_templates() {
+        let (app, handle) = load_map("maps/orthogonal/templates.tmx");
+        let tiled_map = app
+            .world()
+            .resource::<Assets<TiledMap>>()
+            .get(&handle)
+            .expect("map with object templates should be loaded");
+
+        let objects_layer = get_all_layers(&tiled_map.map)
+            .into_iter()
+            .find_map(|(_, layer)| layer.as_object_layer())
+            .expect("map should have an objects layer");
+        let names = objects_layer
+            .objects()
+            .map(|object| object.name.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["Box", "Box", "Tile"]);
+        for object in objects_layer.objects() {
+            let tiled::ObjectShape::Rect { width, height } = object.shape else {
+                panic!("templated objects should be rectangles");
+            };
+            assert_eq!((width, height), (32., 32.));
+        }
+        assert!(objects_layer
+            .get_object(2)
+            .and_then(|object| object.get_tile())
+            .is_some());
+    }
+
+    #[test]
+    fn tile_at_reads_map_data() {
+        let (app, handle) = load_map("maps/orthogonal/finite.tmx");
+        let tiled_map = app
+            .world()
+            .resource::<Assets<TiledMap>>()
+            .get(&handle)
+            .expect("map should be loaded");
+
+        // Third Tiled row, third column: tile ID 6 from the first tileset
+        let expected = tiled_map.tilesets.get(&0).and_then(|t| t.texture_index(6));
+        assert!(expected.is_some());
+        let y_flip = TiledMapYFlip::default();
+        assert_eq!(
+            tiled_map.tile_at("Tile Layer 1", TilePos::new(2, 7), &y_flip),
+            expected
+        );
+        assert!(tiled_map
+            .get_used_tile_ids(0)
+            .is_some_and(|ids| ids.contains(&6)));
+        // Empty tile
+        assert_eq!(
+            tiled_map.tile_at("Tile Layer 1", TilePos::new(1, 8), &y_flip),
+            None
+        );
+        assert_eq!(
+            tiled_map.tile_at("Unknown", TilePos::new(2, 7), &y_flip),
+            None
+        );
+        // Without Y inversion, TilePos matches Tiled coordinates
+        assert_eq!(
+            tiled_map.tile_at("Tile Layer 1", TilePos::new(2, 2), &TiledMapYFlip(false)),
+            expected
+        );
+    }
+
+    #[test]
+    fn layer_metadata_accessors() {
+        let (app, handle) = load_map("maps/orthogonal/finite.tmx");
+        let tiled_map = app
+            .world()
+            .resource::<Assets<TiledMap>>()
+            .get(&handle)
+            .expect("map should be loaded");
+
+        assert_eq!(tiled_map.layer_count(), 3);
+        assert_eq!(
+            tiled_map.layer_names().collect::<Vec<_>>(),
+            vec!["Image Layer 1", "Tile Layer 1", "Collisions"]
+        );
+        assert!(tiled_map
+            .layer_by_name("Tile Layer 1")
+            .and_then(|layer| layer.as_tile_layer())
+            .is_some());
+        assert!(tiled_map.layer_by_name("Unknown").is_none());
+    }
+
+    #[test]
+    fn map_aabb_matches_map_rect() {
+        let (app, handle) = load_map("maps/orthogonal/finite.tmx");
+        let tiled_map = app
+            .world()
+            .resource::<Assets<TiledMap>>()
+            .get(&handle)
+            .expect("map should be loaded");
+
+        let aabb = tiled_map.map_aabb(&GlobalTransform::IDENTITY, &TiledMapAnchor::BottomLeft);
+        assert_eq!(aabb.min, tiled_map.rect.min);
+        assert_eq!(aabb.max, tiled_map.rect.max);
+
+        let map_transform = GlobalTransform::from(Transform::from_xyz(10., 20., 0.));
+        let aabb = tiled_map.map_aabb(&map_transform, &TiledMapAnchor::Center);
+        assert_eq!(aabb.center(), Vec2::new(10., 20.));
+        assert_eq!(aabb.half_size(), tiled_map.rect.half_size());
+    }
+
+    #[test]
+    fn staggered_maps() {
+        for (path, tilemap_size) in [
+            (
+                "maps/isometric/finite_staggered.tmx",
+                TilemapSize::new(10, 10),
+            ),
+            (
+                "maps/isometric/infinite_staggered.tmx",
+                TilemapSize::new(32, 32),
+            ),
+        ] {
+            let (app, handle) = load_map(path);
+            let tiled_map = app
+                .world()
+                .resource::<Assets<TiledMap>>()
+                .get(&handle)
+                .unwrap_or_else(|| panic!("map {path} should be loaded"));
+
+            // Staggered maps are laid out as hexagonal maps: two consecutive rows overlap by half a tile
+            assert_eq!(
+                get_map_type(&tiled_map.map),
+                TilemapType::Hexagon(HexCoordSystem::RowEven)
+            );
+            assert_eq!(tiled_map.tilemap_size, tilemap_size);
+            let (tile_width, tile_height) = (232., 110.);
+            let size = tiled_map.rect.size();
+            assert!((size.x - tilemap_size.x as f32 * tile_width).abs() < 0.01);
+            assert!((size.y - tilemap_size.y as f32 * tile_height / 2.).abs() < 0.01);
+
+            let origin = crate::prelude::from_tiled_position_to_world_space(tiled_map, Vec2::ZERO);
+            assert!(origin.is_finite());
+        }
+    }
+}
@@ -1,7 +1,16 @@
 //! This module contains all map [Component]s definition.
 
-use bevy::{prelude::*, utils::HashMap};
-use tiled::TileId;
+use bevy::{
+    ecs::{component::ComponentId, world::DeferredWorld},
+    math::bounding::Aabb2d,
+    prelude::*,
+    utils::HashMap,
+};
+use bevy_ecs_tilemap::prelude::{
+    TilePos, TileStorage, TilemapId, TilemapSize, TilemapTileSize, TilemapType,
+};
+use std::{collections::BTreeMap, fmt, sync::Arc, time::Duration};
+use tiled::{PropertyValue, TileId};
 
 /// Set the anchor point for associated map or world.
 ///
@@ -29,9 +38,355 @@ impl Default for TiledMapLayerZOffset {
     }
 }
 
+/// Force the render size of tiles.
+///
+/// Can be added to the [Entity] holding the map (or world) to override the tile size computed
+/// from tilesets, for instance to upscale pixel art tiles.
+/// Tiles are still sampled from their tileset texture using their actual size: the tilemaps
+/// [Transform] is scaled instead, so tiles stay aligned with each other.
+///
+/// Objects and physics colliders follow the same scale, computed from the map tile size, so they
+/// stay aligned with tiles. Images layers keep using the size defined in Tiled: to uniformly
+/// scale the whole map, prefer using the map [Transform] scale.
+#[derive(Component, Reflect, Copy, Clone, Debug)]
+#[reflect(Component, Debug)]
+pub struct TiledMapTileSizeOverride(pub TilemapTileSize);
+
+impl TiledMapTileSizeOverride {
+    /// Scale turning the given tile size into the overridden one.
+    pub fn scale(&self, tile_size: Vec2) -> Vec2 {
+        Vec2::new(self.0.x / tile_size.x, self.0.y / tile_size.y)
+    }
+
+    /// Scale applied to objects and physics colliders of the given map.
+    ///
+    /// It turns the map tile size, as defined in Tiled, into the overridden one.
+    pub fn map_scale(&self, map: &tiled::Map) -> Vec2 {
+        self.scale(Vec2::new(map.tile_width as f32, map.tile_height as f32))
+    }
+}
+
+/// Force the [TilemapType] used to render tiles.
+///
+/// Can be added to the [Entity] holding the map (or world) to override the [TilemapType]
+/// computed from the map orientation, for instance to use a different isometric coordinate
+/// system because of how the art is set up.
+///
+/// Note that only tiles layers are affected: objects, images layers and physics colliders
+/// keep using the map orientation defined in Tiled.
+#[derive(Component, Reflect, Copy, Clone, Debug)]
+#[reflect(Component, Debug)]
+pub struct TiledMapTypeOverride(pub TilemapType);
+
+/// Spawn tiles progressively, at most this number of rows per frame.
+///
+/// Can be added to the [Entity] holding the map (or world) to avoid blocking the main thread
+/// when spawning very large maps: all layers and objects are spawned right away but tiles are
+/// spawned by batches of rows, over several frames.
+/// Map events (including [TiledMapCreated](crate::prelude::TiledMapCreated)) are only sent once
+/// the last row of tiles has been spawned.
+/// Meanwhile, spawning progress can be tracked using the [TiledMapSpawnProgress] component.
+#[derive(Component, Reflect, Copy, Clone, Debug)]
+#[reflect(Component, Debug)]
+pub struct TiledMapSpawnRowsPerFrame(pub u32);
+
+/// Do not spawn tile entities.
+///
+/// Can be added to the [Entity] holding the map (or world) when tiles do not need to be
+/// displayed nor queried, for instance for maps only used as a source of physics colliders
+/// or navigation meshes: this saves one [Entity] per tile, which can be a lot for large maps.
+/// Tiles layers entities are still spawned and physics colliders, which are built directly
+/// from the map data, are not affected.
+/// However, since there is no tile entity, no [TiledTileCreated](crate::prelude::TiledTileCreated)
+/// event is sent and tiles are not added to the [TiledMapStorage].
+#[derive(Component, Default, Reflect, Copy, Clone, Debug)]
+#[reflect(Component, Default, Debug)]
+pub struct TiledMapSkipTileEntities;
+
+/// Whether tiles Y coordinates are inverted when spawning tiles.
+///
+/// Can be added to the [Entity] holding the map (or world).
+/// Tiled uses a Y-down coordinate system while Bevy uses a Y-up one: by default (`true`),
+/// tiles of the first Tiled row get the highest [TilePos] Y coordinate.
+/// When `false`, tiles [TilePos] match their Tiled coordinates: tilemaps transform is
+/// mirrored along the Y axis (and tiles vertical flip is toggled) so the map still renders
+/// as it does in Tiled.
+///
+/// Only orthogonal maps are supported: this setting is ignored for other orientations,
+/// including when using a [TiledMapTypeOverride].
+/// [TiledMapLayerStats], [TiledInfiniteLayerOrigin], [TiledMap::tile_at](crate::prelude::TiledMap::tile_at)
+/// and the navigation grid use the same [TilePos] as tiles. Since the map renders the same way,
+/// world positions (of objects, physics colliders or computed using [tile_pos_to_world](crate::prelude::tile_pos_to_world))
+/// are not affected.
+#[derive(Component, Reflect, Copy, Clone, Debug)]
+#[reflect(Component, Default, Debug)]
+pub struct TiledMapYFlip(pub bool);
+
+impl Default for TiledMapYFlip {
+    fn default() -> Self {
+        Self(true)
+    }
+}
+
+impl TiledMapYFlip {
+    /// Returns `true` if tiles of a map rendered using given [TilemapType] get inverted Y coordinates.
+    ///
+    /// Always `true` for non-orthogonal maps, since this setting is ignored for them.
+    pub fn is_inverted(&self, map_type: &TilemapType) -> bool {
+        self.0 || !matches!(map_type, TilemapType::Square)
+    }
+
+    /// Convert a [TilePos] using inverted Y coordinates to the [TilePos] of the same tile
+    /// when using this setting, or the other way around.
+    pub fn convert(
+        &self,
+        tile_pos: TilePos,
+        map_type: &TilemapType,
+        map_size: &TilemapSize,
+    ) -> TilePos {
+        if self.is_inverted(map_type) {
+            tile_pos
+        } else {
+            TilePos::new(tile_pos.x, map_size.y - 1 - tile_pos.y)
+        }
+    }
+}
+
+/// Progress of a map being spawned progressively.
+///
+/// Inserted on the map [Entity] when using [TiledMapSpawnRowsPerFrame] and removed
+/// once all tiles have been spawned.
+#[derive(Component, Default, Reflect, Copy, Clone, Debug)]
+#[reflect(Component, Default, Debug)]
+pub struct TiledMapSpawnProgress {
+    /// Number of rows of tiles which have already been spawned
+    pub spawned_rows: u32,
+    /// Total number of rows of tiles of the map
+    pub total_rows: u32,
+}
+
+impl TiledMapSpawnProgress {
+    /// Fraction of the map rows which have been spawned, between `0.` and `1.`
+    pub fn fraction(&self) -> f32 {
+        if self.total_rows == 0 {
+            return 1.;
+        }
+        self.spawned_rows as f32 / self.total_rows as f32
+    }
+}
+
+/// Custom Z computation for Tiled layers.
+///
+/// Can be added to the [Entity] holding the map (or world) to override [TiledMapLayerZOffset].
+/// The function receives the layer index and the layer itself and returns the layer Z position,
+/// relative to the map.
+///
+/// Example:
+/// ```rust,no_run
+/// use bevy::prelude::*;
+/// use bevy_ecs_tiled::prelude::*;
+///
+/// fn startup(mut commands: Commands, asset_server: Res<AssetServer>) {
+///     commands.spawn((
+///         TiledMapHandle(asset_server.load("map.tmx")),
+///         TiledMapLayerZFn::from_tiled_property("z_offset"),
+///     ));
+/// }
+/// ```
+#[derive(Component, Clone)]
+pub struct TiledMapLayerZFn(pub Arc<dyn Fn(usize, &tiled::Layer<'_>) -> f32 + Send + Sync>);
+
+impl TiledMapLayerZFn {
+    /// Layers are stacked in the same order as in Tiled, with `spacing` between two consecutive layers.
+    ///
+    /// As when using [TiledMapLayerZOffset], last layer is at Z = 0 and previous layers are below it:
+    /// `TiledMapLayerZFn::linear(offset)` gives the same result as `TiledMapLayerZOffset(offset)`.
+    pub fn linear(spacing: f32) -> Self {
+        Self(Arc::new(move |index: usize, layer: &tiled::Layer<'_>| {
+            let layers_count = super::utils::get_all_layers(layer.map()).len();
+            (index as f32 + 1. - layers_count as f32) * spacing
+        }))
+    }
+
+    /// Layers are stacked in the reverse order from Tiled, with `spacing` between two consecutive layers.
+    ///
+    /// First layer is at Z = 0 and is above all other layers.
+    pub fn reverse(spacing: f32) -> Self {
+        Self(Arc::new(move |index: usize, _: &tiled::Layer<'_>| {
+            -(index as f32) * spacing
+        }))
+    }
+
+    /// Read layer Z from the given layer custom property.
+    ///
+    /// Property can either be a `float` or an `int`. Layers without this property are at Z = 0.
+    pub fn from_tiled_property(property_name: &str) -> Self {
+        let property_name = property_name.to_string();
+        Self(Arc::new(
+            move |_: usize, layer: &tiled::Layer<'_>| match layer.properties.get(&property_name) {
+                Some(PropertyValue::FloatValue(z)) => *z,
+                Some(PropertyValue::IntValue(z)) => *z as f32,
+                _ => 0.,
+            },
+        ))
+    }
+}
+
+impl fmt::Debug for TiledMapLayerZFn {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("TiledMapLayerZFn").finish_non_exhaustive()
+    }
+}
+
+/// Custom initial [Visibility] for Tiled layers.
+///
+/// Can be added to the [Entity] holding the map (or world) to decide, when a layer is spawned,
+/// whether it should be visible or not instead of using the layer visibility from Tiled.
+///
+/// Example:
+/// ```rust,no_run
+/// use bevy::prelude::*;
+/// use bevy_ecs_tiled::prelude::*;
+///
+/// fn startup(mut commands: Commands, asset_server: Res<AssetServer>) {
+///     commands.spawn((
+///         TiledMapHandle(asset_server.load("map.tmx")),
+///         // Hide the fog of war layer until the player reaches it
+///         TiledMapLayerVisibilityFn::new(|layer| match layer.name.as_str() {
+///             "FogOfWar" => Visibility::Hidden,
+///             _ => Visibility::Inherited,
+///         }),
+///     ));
+/// }
+/// ```
+#[derive(Component, Clone)]
+pub struct TiledMapLayerVisibilityFn(
+    pub Arc<dyn Fn(&tiled::Layer<'_>) -> Visibility + Send + Sync>,
+);
+
+impl TiledMapLayerVisibilityFn {
+    /// Create a new [TiledMapLayerVisibilityFn] from the given function.
+    pub fn new(f: impl Fn(&tiled::Layer<'_>) -> Visibility + Send + Sync + 'static) -> Self {
+        Self(Arc::new(f))
+    }
+}
+
+impl fmt::Debug for TiledMapLayerVisibilityFn {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("TiledMapLayerVisibilityFn")
+            .finish_non_exhaustive()
+    }
+}
+
+/// Filter which layers of a map are spawned.
+///
+/// Can be added to the [Entity] holding the map (or world) to only spawn some of the map layers.
+/// Layers nested in a spawned group layer are always spawned. Layers which are filtered out are
+/// not spawned at all, neither are their tiles or objects: custom properties referencing an
+/// object from a filtered out layer are set to [Entity::PLACEHOLDER] (or [None] for an
+/// `Option<Entity>`) and a warning is logged.
+///
+/// Example:
+/// ```rust,no_run
+/// use bevy::prelude::*;
+/// use bevy_ecs_tiled::prelude::*;
+///
+/// fn startup(mut commands: Commands, asset_server: Res<AssetServer>) {
+///     commands.spawn((
+///         TiledMapHandle(asset_server.load("map.tmx")),
+///         TiledMapLayerFilter::ByName(vec!["collision".into(), "ground".into()]),
+///     ));
+/// }
+/// ```
+#[derive(Component, Default, Reflect, Clone, PartialEq, Eq, Debug)]
+#[reflect(Component, Default, Debug, PartialEq)]
+pub enum TiledMapLayerFilter {
+    /// Spawn all layers.
+    #[default]
+    All,
+    /// Only spawn layers with one of the given names.
+    ByName(Vec<String>),
+    /// Only spawn layers with one of the given classes.
+    ByClass(Vec<String>),
+    /// Only spawn layers with one of the given IDs.
+    ///
+    /// Layer IDs are the ones used by [TiledLayerCreated](crate::prelude::TiledLayerCreated),
+    /// see [get_all_layers](crate::prelude::get_all_layers).
+    ByIndex(Vec<usize>),
+}
+
+impl TiledMapLayerFilter {
+    /// Check if the given layer, with given ID, should be spawned.
+    pub fn matches(&self, layer_id: usize, layer: &tiled::Layer<'_>) -> bool {
+        match self {
+            Self::All => true,
+            Self::ByName(names) => names.iter().any(|name| *name == layer.name),
+            Self::ByClass(classes) => layer
+                .user_type
+                .as_ref()
+                .is_some_and(|class| classes.contains(class)),
+            Self::ByIndex(indices) => indices.contains(&layer_id),
+        }
+    }
+}
+
+/// Custom hook called once a map has been spawned.
+///
+/// Can be added to the [Entity] holding the map (or world) to run arbitrary code with exclusive
+/// [World] access, once all layers, tiles and objects of the map have been spawned and before
+/// events are sent and observers are triggered for [TiledMapCreated](crate::prelude::TiledMapCreated).
+/// The hook receives the map [Entity].
+///
+/// The hook should not despawn the map [Entity]: if it does, an error is logged
+/// and no event is sent for this map.
+///
+/// Example:
+/// ```rust,no_run
+/// use bevy::prelude::*;
+/// use bevy_ecs_tiled::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Walkable;
+///
+/// fn startup(mut commands: Commands, asset_server: Res<AssetServer>) {
+///     commands.spawn((
+///         TiledMapHandle(asset_server.load("map.tmx")),
+///         TiledMapPostSpawnHook::new(|world, _map_entity| {
+///             let tiles: Vec<Entity> = world
+///                 .query_filtered::<Entity, With<TiledMapTile>>()
+///                 .iter(world)
+///                 .collect();
+///             for tile in tiles {
+///                 world.entity_mut(tile).insert(Walkable);
+///             }
+///         }),
+///     ));
+/// }
+/// ```
+#[derive(Component, Clone)]
+pub struct TiledMapPostSpawnHook(pub Arc<dyn Fn(&mut World, Entity) + Send + Sync>);
+
+impl TiledMapPostSpawnHook {
+    /// Create a new [TiledMapPostSpawnHook] from the given function.
+    pub fn new(f: impl Fn(&mut World, Entity) + Send + Sync + 'static) -> Self {
+        Self(Arc::new(f))
+    }
+}
+
+impl fmt::Debug for TiledMapPostSpawnHook {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("TiledMapPostSpawnHook")
+            .finish_non_exhaustive()
+    }
+}
+
 /// Marker [Component] to trigger a map respawn.
 ///
 /// Must be added to the [Entity] holding the map.
+/// All layers, tiles and objects are despawned then spawned again from the already loaded
+/// [TiledMap](crate::prelude::TiledMap) asset, the same way as when the map is hot-reloaded.
+/// The `.tmx` file is not read again from disk.
+/// The equivalent [Component] for worlds is [RespawnTiledWorld](crate::prelude::RespawnTiledWorld).
 ///
 /// Example:
 /// ```rust,no_run
@@ -50,6 +405,17 @@ impl Default for TiledMapLayerZOffset {
 #[reflect(Component, Default, Debug)]
 pub struct RespawnTiledMap;
 
+/// Optional user-defined stable identifier for a Tiled map.
+///
+/// Unlike the [TiledMapHandle](crate::prelude::TiledMapHandle) [AssetId], this identifier
+/// does not change between sessions and can be used to reference a map from serialized game state.
+/// It can be inserted alongside the [TiledMapHandle](crate::prelude::TiledMapHandle) on the map [Entity].
+///
+/// Maps spawned from a Tiled world automatically get one, using the map file name from the `.world` file.
+#[derive(Component, Default, Reflect, Clone, PartialEq, Eq, Hash, Debug)]
+#[reflect(Component, Default, Debug)]
+pub struct TiledMapId(pub String);
+
 /// [Component] storing all the Tiled items composing this map.
 /// Makes the association between Tiled ID and corresponding Bevy [Entity].
 ///
@@ -58,7 +424,12 @@ pub struct RespawnTiledMap;
 #[reflect(Component, Default, Debug)]
 pub struct TiledMapStorage {
     /// Map of layers entities, using their Tiled ID as key
-    pub layers: HashMap<u32, Entity>,
+    ///
+    /// It contains all layers, including group layers and the layers nested in them.
+    /// Iteration order is deterministic: layers are sorted by their Tiled ID, which reflects
+    /// their creation order in Tiled rather than their order in the map
+    /// (use [get_all_layers](crate::prelude::get_all_layers) for that).
+    pub layers: BTreeMap<u32, Entity>,
     /// Map of objects entities, using their Tiled ID as key
     pub objects: HashMap<u32, Entity>,
     /// Map of tiles entities, using the name of the tileset
@@ -69,13 +440,77 @@ pub struct TiledMapStorage {
     pub tiles: HashMap<(String, TileId), Vec<Entity>>,
 }
 
+/// Tiled class of a map, layer or object.
+///
+/// Inserted on the map, layers and objects entities which have a non-empty class in Tiled, so
+/// they can be queried using `With<TiledClass>` or filtered by class value.
+/// Tilesets class is held by a distinct [TiledTilesetClass] component.
+///
+/// Example:
+/// ```rust,no_run
+/// use bevy::prelude::*;
+/// use bevy_ecs_tiled::prelude::*;
+///
+/// fn find_doors(query: Query<(Entity, &TiledClass), With<TiledMapObject>>) {
+///     for (entity, class) in query.iter().filter(|(_, class)| class.0 == "Door") {
+///         info!("Found door {entity:?} (class = {})", class.0);
+///     }
+/// }
+/// ```
+#[derive(Component, Default, Reflect, Clone, PartialEq, Eq, Hash, Debug)]
+#[reflect(Component, Default, Debug, PartialEq, Hash)]
+pub struct TiledClass(pub String);
+
+impl TiledClass {
+    /// Create a [TiledClass] from a Tiled class, returns [None] if the class is empty.
+    pub fn from_class(class: &str) -> Option<Self> {
+        (!class.is_empty()).then(|| Self(class.to_string()))
+    }
+}
+
+/// Tiled class of a tileset.
+///
+/// Inserted on [TiledMapTileLayerForTileset] entities whose tileset has a non-empty class in
+/// Tiled. It is distinct from [TiledClass] so that querying `With<TiledClass>` does not mix up
+/// tilesets with the layer they belong to.
+#[derive(Component, Default, Reflect, Clone, PartialEq, Eq, Hash, Debug)]
+#[reflect(Component, Default, Debug, PartialEq, Hash)]
+pub struct TiledTilesetClass(pub String);
+
+impl TiledTilesetClass {
+    /// Create a [TiledTilesetClass] from a Tiled class, returns [None] if the class is empty.
+    pub fn from_class(class: &str) -> Option<Self> {
+        (!class.is_empty()).then(|| Self(class.to_string()))
+    }
+}
+
 /// Marker [Component] for a Tiled map.
+///
+/// Inserted on the map [Entity] once all its layers, tiles and objects have been spawned,
+/// right before [TiledMapCreated](crate::prelude::TiledMapCreated) is sent. When using
+/// [TiledMapSpawnRowsPerFrame], it is only inserted once the last row of tiles is spawned.
+/// It is removed while the map is being respawned.
 #[derive(Component, Default, Reflect, Copy, Clone, Debug)]
 #[reflect(Component, Default, Debug)]
 #[require(Visibility, Transform)]
 pub struct TiledMapMarker;
 
+/// World-space axis-aligned bounding box of a Tiled map.
+///
+/// Automatically inserted on the map [Entity] once the map is spawned and updated whenever
+/// the map [GlobalTransform] or [TiledMapAnchor] changes.
+/// Layers offset are not taken into account.
+#[derive(Component, Reflect, Copy, Clone, Debug)]
+#[reflect(Component, Debug)]
+pub struct TiledMapBounds {
+    /// Map bounding box, in world space
+    pub aabb: Aabb2d,
+}
+
 /// Marker [Component] for a Tiled map layer.
+///
+/// Required [Transform] and [Visibility] components respectively pull [GlobalTransform] and
+/// [InheritedVisibility], so layers are always part of the transform and visibility hierarchies.
 #[derive(Component, Default, Reflect, Copy, Clone, Debug)]
 #[reflect(Component, Default, Debug)]
 #[require(Visibility, Transform)]
@@ -87,6 +522,71 @@ pub struct TiledMapLayer;
 #[require(Visibility, Transform)]
 pub struct TiledMapTileLayer;
 
+/// Statistics about the tiles of a Tiled map tile layer.
+///
+/// Inserted alongside the [TiledMapTileLayer] marker.
+#[derive(Component, Default, Reflect, Copy, Clone, Debug)]
+#[reflect(Component, Default, Debug)]
+pub struct TiledMapLayerStats {
+    /// Number of non-empty tiles in this layer
+    pub tile_count: u32,
+    /// Number of distinct tiles used in this layer
+    pub unique_tile_ids: u32,
+    /// Bounding box of the non-empty tiles of this layer, in tile space (ie. using [TilePos](bevy_ecs_tilemap::prelude::TilePos) coordinates)
+    ///
+    /// [None] if the layer is empty.
+    pub bounding_rect: Option<Rect>,
+}
+
+/// Origin of an infinite Tiled map tile layer.
+///
+/// Inserted on infinite tile layers entities, alongside the [TiledMapTileLayer] marker.
+/// Since `bevy_ecs_tilemap` does not support negative tile coordinates, tiles of infinite maps
+/// are shifted so that the top-left tile of the map ends up at index (0, 0).
+/// This component holds the Tiled coordinates of this top-left tile.
+#[derive(Component, Default, Reflect, Copy, Clone, PartialEq, Eq, Debug)]
+#[reflect(Component, Default, Debug, PartialEq)]
+pub struct TiledInfiniteLayerOrigin {
+    /// Tiled X coordinate of the map top-left tile
+    pub tile_x: i32,
+    /// Tiled Y coordinate of the map top-left tile
+    pub tile_y: i32,
+    /// Whether tiles [TilePos] Y coordinates follow Tiled ones, ie. the map uses [TiledMapYFlip] `(false)`
+    pub tiled_y: bool,
+}
+
+impl TiledInfiniteLayerOrigin {
+    /// Convert Tiled tile coordinates to a [TilePos].
+    ///
+    /// Returns [None] if the tile is outside of the map.
+    pub fn to_tile_pos(
+        &self,
+        tiled_x: i32,
+        tiled_y: i32,
+        map_size: &TilemapSize,
+    ) -> Option<TilePos> {
+        let x = u32::try_from(tiled_x - self.tile_x).ok()?;
+        let y = u32::try_from(tiled_y - self.tile_y).ok()?;
+        (x < map_size.x && y < map_size.y).then(|| TilePos::new(x, self.flip_y(y, map_size)))
+    }
+
+    /// Convert a [TilePos] to Tiled tile coordinates.
+    pub fn to_tiled_coords(&self, tile_pos: &TilePos, map_size: &TilemapSize) -> IVec2 {
+        IVec2::new(
+            self.tile_x + tile_pos.x as i32,
+            self.tile_y + self.flip_y(tile_pos.y, map_size) as i32,
+        )
+    }
+
+    fn flip_y(&self, y: u32, map_size: &TilemapSize) -> u32 {
+        if self.tiled_y {
+            y
+        } else {
+            map_size.y - 1 - y
+        }
+    }
+}
+
 /// Marker [Component] for a Tiled map tile layer for a given tileset.
 #[derive(Component, Default, Reflect, Copy, Clone, Debug)]
 #[reflect(Component, Default, Debug)]
@@ -99,12 +599,73 @@ pub struct TiledMapTileLayerForTileset;
 #[require(Visibility, Transform)]
 pub struct TiledMapObjectLayer;
 
+/// Tiled drawing order for objects of an object layer.
+#[derive(Default, Reflect, Copy, Clone, PartialEq, Eq, Debug)]
+#[reflect(Default, Debug, PartialEq)]
+pub enum TiledDrawOrder {
+    /// Objects are sorted by their Y coordinate (Tiled default).
+    #[default]
+    TopDown,
+    /// Objects are drawn in the order they appear in the layer.
+    Index,
+}
+
+/// [Component] holding Tiled object layer informations.
+///
+/// Inserted on object layers entities, alongside the [TiledMapObjectLayer] marker.
+#[derive(Component, Default, Reflect, Copy, Clone, Debug)]
+#[reflect(Component, Default, Debug)]
+pub struct TiledObjectLayerData {
+    /// Drawing order for objects of this layer
+    pub draw_order: TiledDrawOrder,
+    /// Layer color, if any.
+    ///
+    /// It is applied as a tint on all objects [Sprite] of this layer.
+    pub color: Option<Color>,
+    /// Number of objects in this layer
+    pub object_count: usize,
+}
+
+/// [Component] storing all the objects of a Tiled object layer.
+/// Makes the association between Tiled object ID and corresponding Bevy [Entity].
+///
+/// Inserted on object layers entities, alongside the [TiledMapObjectLayer] marker.
+/// Combined with [TiledMapStorage], it allows to retrieve an object from a given layer.
+#[derive(Component, Default, Reflect, Clone, Debug)]
+#[reflect(Component, Default, Debug)]
+pub struct TiledObjectLayerStorage(pub HashMap<u32, Entity>);
+
+impl TiledObjectLayerStorage {
+    /// Retrieve the [Entity] of an object using its Tiled ID.
+    pub fn get_object(&self, id: u32) -> Option<Entity> {
+        self.0.get(&id).copied()
+    }
+
+    /// Iterate over all objects of this layer, with their Tiled ID and [Entity].
+    pub fn iter_objects(&self) -> impl Iterator<Item = (u32, Entity)> + '_ {
+        self.0.iter().map(|(id, entity)| (*id, *entity))
+    }
+}
+
 /// Marker [Component] for a Tiled map group layer.
 #[derive(Component, Default, Reflect, Copy, Clone, Debug)]
 #[reflect(Component, Default, Debug)]
 #[require(Visibility, Transform)]
 pub struct TiledMapGroupLayer;
 
+/// Data of a Tiled map group layer.
+///
+/// Inserted on group layers entities, alongside the [TiledMapGroupLayer] marker.
+/// Layers nested in a group layer are spawned as children of the group layer entity.
+#[derive(Component, Default, Reflect, Clone, Debug)]
+#[reflect(Component, Default, Debug)]
+pub struct TiledGroupLayer {
+    /// Tiled ID of this group layer
+    pub id: u32,
+    /// Name of this group layer
+    pub name: String,
+}
+
 /// Marker [Component] for a Tiled map image layer.
 #[derive(Component, Default, Reflect, Copy, Clone, Debug)]
 #[reflect(Component, Default, Debug)]
@@ -117,22 +678,279 @@ pub struct TiledMapImageLayer;
 /// It would be useless to add these components to tile entities:
 /// - it will not do what you think: rendering is done at the [TiledMapTileLayerForTileset] level through `TilemapBundle` from `bevy_ecs_tilemap`
 /// - it could impact performances pretty badly since it would mean to compute both [GlobalTransform] and [InheritedVisibility] for all tiles
+///
+/// When this component is removed, for instance because the tile is despawned, the tile is also
+/// removed from its tilemap [TileStorage].
 #[derive(Component, Default, Reflect, Copy, Clone, Debug)]
 #[reflect(Component, Default, Debug)]
+#[component(on_remove = remove_tile_from_storage)]
 pub struct TiledMapTile;
 
+/// Remove a tile from its tilemap [TileStorage], so it does not leave a dangling [Entity] behind.
+///
+/// Tile [TilePos] and [TilemapId] are still available when this hook runs, so we only have to
+/// touch the [TileStorage] which actually held the tile.
+fn remove_tile_from_storage(mut world: DeferredWorld, entity: Entity, _: ComponentId) {
+    let (Some(&tile_pos), Some(&tilemap_id)) =
+        (world.get::<TilePos>(entity), world.get::<TilemapId>(entity))
+    else {
+        return;
+    };
+    let Some(mut tile_storage) = world.get_mut::<TileStorage>(tilemap_id.0) else {
+        return;
+    };
+    if tile_storage.checked_get(&tile_pos) == Some(entity) {
+        tile_storage.checked_remove(&tile_pos);
+    }
+}
+
 /// Marker [Component] for a Tiled map object.
 #[derive(Component, Default, Reflect, Copy, Clone, Debug)]
 #[reflect(Component, Default, Debug)]
 #[require(Visibility, Transform)]
 pub struct TiledMapObject;
 
+/// [Component] holding generic informations about a Tiled object.
+///
+/// Inserted on all objects entities, alongside the [TiledMapObject] marker.
+/// Object shape is available through the [TiledObjectShape] component.
+#[derive(Component, Default, Reflect, Clone, PartialEq, Eq, Debug)]
+#[reflect(Component, Default, Debug)]
+pub struct TiledObjectData {
+    /// Tiled ID of the object, unique across the whole map
+    pub id: u32,
+    /// Name of the object
+    pub name: String,
+    /// Class of the object, empty if not set
+    pub class: String,
+    /// Whether the object is visible in Tiled
+    pub visible: bool,
+}
+
+/// [Component] holding the shape of a Tiled object.
+///
+/// Inserted on all objects entities, alongside the [TiledMapObject] marker.
+///
+/// All coordinates use Bevy referential (ie. Y axis is pointing up) and are relative to the
+/// object [Transform]: Tiled objects origin is their top-left corner (except for ellipses
+/// where it's the top-left corner of their bounding box and for tile objects where it's their
+/// bottom-left corner).
+/// Use [TiledObjectShape::world_points] to get points in world space.
+#[derive(Component, Default, Reflect, Clone, PartialEq, Debug)]
+#[reflect(Component, Default, Debug)]
+pub enum TiledObjectShape {
+    /// A rectangle, extending to the right and to the bottom of the object origin.
+    Rect {
+        /// Width of the rectangle
+        width: f32,
+        /// Height of the rectangle
+        height: f32,
+    },
+    /// An ellipse, contained in a bounding box extending to the right and to the bottom of the object origin.
+    Ellipse {
+        /// Width of the ellipse bounding box
+        width: f32,
+        /// Height of the ellipse bounding box
+        height: f32,
+    },
+    /// An open polyline.
+    Polyline {
+        /// Polyline points, relative to the object [Transform]
+        local_points: Vec<Vec2>,
+    },
+    /// A closed polygon.
+    Polygon {
+        /// Polygon points, relative to the object [Transform]
+        local_points: Vec<Vec2>,
+    },
+    /// A single point, located at the object origin.
+    #[default]
+    Point,
+    /// A text box, extending to the right and to the bottom of the object origin.
+    Text {
+        /// Width of the text box
+        width: f32,
+        /// Height of the text box
+        height: f32,
+    },
+    /// A tile object, extending to the right and to the top of the object origin.
+    Tile {
+        /// Width of the tile object
+        width: f32,
+        /// Height of the tile object
+        height: f32,
+    },
+}
+
+impl From<&tiled::ObjectShape> for TiledObjectShape {
+    fn from(shape: &tiled::ObjectShape) -> Self {
+        let to_local_points = |points: &Vec<(f32, f32)>| -> Vec<Vec2> {
+            points.iter().map(|(x, y)| Vec2::new(*x, -*y)).collect()
+        };
+        match shape {
+            tiled::ObjectShape::Rect { width, height } => Self::Rect {
+                width: *width,
+                height: *height,
+            },
+            tiled::ObjectShape::Ellipse { width, height } => Self::Ellipse {
+                width: *width,
+                height: *height,
+            },
+            tiled::ObjectShape::Polyline { points } => Self::Polyline {
+                local_points: to_local_points(points),
+            },
+            tiled::ObjectShape::Polygon { points } => Self::Polygon {
+                local_points: to_local_points(points),
+            },
+            tiled::ObjectShape::Point(_, _) => Self::Point,
+            tiled::ObjectShape::Text { width, height, .. } => Self::Text {
+                width: *width,
+                height: *height,
+            },
+        }
+    }
+}
+
+impl TiledObjectShape {
+    /// Create the shape of given Tiled object.
+    ///
+    /// Unlike converting the object [tiled::ObjectShape], this takes into account that tile
+    /// objects origin is their bottom-left corner.
+    pub fn from_object(object: &tiled::Object) -> Self {
+        match (&object.shape, object.get_tile()) {
+            (tiled::ObjectShape::Rect { width, height }, Some(_)) => Self::Tile {
+                width: *width,
+                height: *height,
+            },
+            (shape, _) => Self::from(shape),
+        }
+    }
+
+    /// Retrieve the points of this shape, relative to the object [Transform].
+    ///
+    /// For rectangles, ellipses, text boxes and tile objects, returns the four corners of their
+    /// bounding box.
+    pub fn local_points(&self) -> Vec<Vec2> {
+        match self {
+            Self::Rect { width, height }
+            | Self::Ellipse { width, height }
+            | Self::Text { width, height } => vec![
+                Vec2::ZERO,
+                Vec2::new(*width, 0.),
+                Vec2::new(*width, -*height),
+                Vec2::new(0., -*height),
+            ],
+            Self::Tile { width, height } => vec![
+                Vec2::ZERO,
+                Vec2::new(*width, 0.),
+                Vec2::new(*width, *height),
+                Vec2::new(0., *height),
+            ],
+            Self::Polyline { local_points } | Self::Polygon { local_points } => {
+                local_points.clone()
+            }
+            Self::Point => vec![Vec2::ZERO],
+        }
+    }
+
+    /// Retrieve the points of this shape in world space, given the object [GlobalTransform].
+    pub fn world_points(&self, object_transform: &GlobalTransform) -> Vec<Vec2> {
+        self.local_points()
+            .into_iter()
+            .map(|p| object_transform.transform_point(p.extend(0.)).truncate())
+            .collect()
+    }
+}
+
+/// Axis-aligned bounding box of a Tiled object.
+///
+/// Inserted alongside the [TiledMapObject] marker for all objects, whatever their shape:
+/// for polygons and polylines, it's the bounding box of all their vertices and for points
+/// it's a zero-size [Rect].
+/// Coordinates are in map-local space, ie. relative to the map [Entity] [Transform],
+/// using Bevy referential. Object rotation is taken into account.
+#[derive(Component, Default, Reflect, Copy, Clone, PartialEq, Debug)]
+#[reflect(Component, Default, Debug)]
+pub struct TiledObjectBounds(pub Rect);
+
+impl TiledObjectBounds {
+    /// Check if given world space point is inside these bounds, given the map [GlobalTransform].
+    pub fn contains_world_point(&self, point: Vec2, map_transform: &GlobalTransform) -> bool {
+        let local = map_transform
+            .affine()
+            .inverse()
+            .transform_point3(point.extend(0.));
+        self.0.contains(local.truncate())
+    }
+}
+
 /// Marker [Component] for the [Sprite] attached to an image layer.
 #[derive(Component, Default, Reflect, Copy, Clone, Debug)]
 #[reflect(Component, Default, Debug)]
 #[require(Visibility, Transform)]
 pub struct TiledMapImage;
 
+/// Repeat flags of a Tiled image layer.
+///
+/// Inserted alongside the [TiledMapImage] [Sprite] when the image layer is set to repeat
+/// along at least one axis. The [Sprite] itself is not repeated: it is up to the user to
+/// tile it, for instance to implement a parallax background.
+#[derive(Component, Default, Reflect, Copy, Clone, Debug)]
+#[reflect(Component, Default, Debug)]
+pub struct TiledImageLayerRepeat {
+    /// Image should be repeated along the X axis
+    pub x: bool,
+    /// Image should be repeated along the Y axis
+    pub y: bool,
+}
+
+/// Parallax factors of a Tiled layer.
+///
+/// Inserted on layers entities which have a parallax factor different from `1.0` along at least
+/// one axis. Every frame, the layer [Transform] is offset by `camera_pos * (1.0 - factor)`, where
+/// `camera_pos` is the camera position relative to the map origin.
+/// As in Tiled, the parallax factor of a group layer is multiplied with the ones of its children.
+///
+/// The camera used is the one with the [TiledWorldChunkingCamera](crate::prelude::TiledWorldChunkingCamera) marker if any,
+/// or the first [Camera] found otherwise.
+/// Remove this component to disable parallax scrolling for a given layer.
+#[derive(Component, Reflect, Copy, Clone, PartialEq, Debug)]
+#[reflect(Component, Default, Debug)]
+pub struct TiledParallaxLayer {
+    /// Horizontal parallax factor of the layer
+    pub factor_x: f32,
+    /// Vertical parallax factor of the layer
+    pub factor_y: f32,
+    /// Parallax factor of the parent group layers of this layer
+    pub(crate) inherited_factor: Vec2,
+    /// Parallax factor already applied by the parent [Entity] of this layer
+    pub(crate) parent_factor: Vec2,
+    /// Translation of the layer relative to its parent, without parallax offset
+    pub(crate) origin: Vec2,
+}
+
+impl Default for TiledParallaxLayer {
+    fn default() -> Self {
+        Self {
+            factor_x: 1.,
+            factor_y: 1.,
+            inherited_factor: Vec2::ONE,
+            parent_factor: Vec2::ONE,
+            origin: Vec2::ZERO,
+        }
+    }
+}
+
+impl TiledParallaxLayer {
+    /// Compute the layer offset relative to its parent for given camera position,
+    /// expressed relative to the map origin.
+    pub fn offset(&self, camera_pos: Vec2) -> Vec2 {
+        // Parent group layers already applied their own offset: only add the difference
+        let factor = self.inherited_factor * Vec2::new(self.factor_x, self.factor_y);
+        camera_pos * (self.parent_factor - factor)
+    }
+}
+
 /// This [Component] is used for animated objects.
 /// We will automatically update the Sprite index every time the timer fires.
 #[derive(Component, Default, Reflect, Clone, Debug)]
@@ -146,3 +964,189 @@ pub struct TiledAnimation {
     /// Timer firing every time we should update the frame
     pub timer: Timer,
 }
+
+/// This [Component] is used for animated tiles which cannot be animated by `bevy_ecs_tilemap`.
+///
+/// It is inserted on tiles entities whose animation frames have different durations or do not
+/// use consecutive tiles: we will update the tile [TileTextureIndex](bevy_ecs_tilemap::prelude::TileTextureIndex)
+/// every time the frame changes.
+/// Other animated tiles use `bevy_ecs_tilemap` `AnimatedTile` component instead.
+///
+/// Elapsed time is tracked as a [Duration] rather than a float, so tiles sharing the same
+/// animation stay in sync.
+#[derive(Component, Default, Reflect, Clone, Debug)]
+#[reflect(Component, Default, Debug)]
+pub struct TiledTileAnimation {
+    /// Animation frames: texture index and duration (in milliseconds)
+    pub frames: Vec<(u32, u32)>,
+    /// Should the animation restart once it reaches its last frame or stop on it
+    pub looping: bool,
+    /// Time elapsed since the animation started
+    pub elapsed: Duration,
+    /// Index of the current frame
+    pub current_frame: usize,
+}
+
+impl TiledTileAnimation {
+    /// Compute the frame which should be displayed after given elapsed time.
+    pub fn frame_at(&self, elapsed: Duration) -> usize {
+        let total: u128 = self.frames.iter().map(|(_, d)| *d as u128).sum();
+        if total == 0 {
+            return 0;
+        }
+        let mut elapsed = elapsed.as_millis();
+        if self.looping {
+            elapsed %= total;
+        }
+        let mut frame_end = 0;
+        for (index, (_, duration)) in self.frames.iter().enumerate() {
+            frame_end += *duration as u128;
+            if elapsed < frame_end {
+                return index;
+            }
+        }
+        self.frames.len() - 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn infinite_layer_origin() {
+        let origin = TiledInfiniteLayerOrigin {
+            tile_x: -16,
+            tile_y: -32,
+            tiled_y: false,
+        };
+        let map_size = TilemapSize { x: 64, y: 64 };
+        assert_eq!(
+            origin.to_tile_pos(-16, -32, &map_size),
+            Some(TilePos::new(0, 63))
+        );
+        assert_eq!(
+            origin.to_tile_pos(0, 0, &map_size),
+            Some(TilePos::new(16, 31))
+        );
+        assert_eq!(origin.to_tile_pos(-17, 0, &map_size), None);
+        assert_eq!(origin.to_tile_pos(48, 0, &map_size), None);
+        assert_eq!(
+            origin.to_tiled_coords(&TilePos::new(16, 31), &map_size),
+            IVec2::new(0, 0)
+        );
+
+        // Without Y inversion, TilePos follows Tiled coordinates
+        let origin = TiledInfiniteLayerOrigin {
+            tiled_y: true,
+            ..origin
+        };
+        assert_eq!(
+            origin.to_tile_pos(0, 0, &map_size),
+            Some(TilePos::new(16, 32))
+        );
+        assert_eq!(
+            origin.to_tiled_coords(&TilePos::new(16, 32), &map_size),
+            IVec2::new(0, 0)
+        );
+    }
+
+    #[test]
+    fn polygon_points_from_tiled() {
+        let shape = TiledObjectShape::from(&tiled::ObjectShape::Polygon {
+            points: vec![(0., 0.), (10., 10.), (0., 10.)],
+        });
+        assert_eq!(
+            shape,
+            TiledObjectShape::Polygon {
+                local_points: vec![Vec2::new(0., 0.), Vec2::new(10., -10.), Vec2::new(0., -10.)]
+            }
+        );
+    }
+
+    #[test]
+    fn tile_object_points_extend_upward() {
+        let rect = TiledObjectShape::Rect {
+            width: 16.,
+            height: 8.,
+        };
+        assert!(rect.local_points().iter().all(|p| p.y <= 0.));
+        let tile = TiledObjectShape::Tile {
+            width: 16.,
+            height: 8.,
+        };
+        assert_eq!(
+            tile.local_points(),
+            vec![
+                Vec2::ZERO,
+                Vec2::new(16., 0.),
+                Vec2::new(16., 8.),
+                Vec2::new(0., 8.)
+            ]
+        );
+    }
+
+    #[test]
+    fn polygon_points_in_world_space() {
+        let shape = TiledObjectShape::Polygon {
+            local_points: vec![Vec2::new(10., 10.)],
+        };
+        let transform = GlobalTransform::from_xyz(100., 100., 0.);
+        assert_eq!(shape.world_points(&transform), vec![Vec2::new(110., 110.)]);
+    }
+
+    #[test]
+    fn tile_animation_frames() {
+        let mut animation = TiledTileAnimation {
+            frames: vec![(3, 100), (7, 50), (1, 200)],
+            looping: true,
+            ..default()
+        };
+        assert_eq!(animation.frame_at(Duration::from_millis(0)), 0);
+        assert_eq!(animation.frame_at(Duration::from_millis(120)), 1);
+        assert_eq!(animation.frame_at(Duration::from_millis(150)), 2);
+        assert_eq!(animation.frame_at(Duration::from_millis(470)), 1);
+
+        animation.looping = false;
+        assert_eq!(animation.frame_at(Duration::from_millis(470)), 2);
+    }
+
+    #[test]
+    fn parallax_layer_offset() {
+        let camera_pos = Vec2::new(100., 40.);
+        let layer = TiledParallaxLayer {
+            factor_x: 0.5,
+            factor_y: 0.,
+            ..default()
+        };
+        assert_eq!(layer.offset(camera_pos), Vec2::new(50., 40.));
+
+        // Nested in a group layer with a 0.5 factor which already offsets its children
+        let nested = TiledParallaxLayer {
+            factor_x: 0.5,
+            factor_y: 1.,
+            inherited_factor: Vec2::splat(0.5),
+            parent_factor: Vec2::splat(0.5),
+            ..default()
+        };
+        assert_eq!(nested.offset(camera_pos), Vec2::new(25., 0.));
+    }
+
+    #[test]
+    fn layers_have_transform_and_visibility_hierarchy() {
+        let mut world = World::new();
+        for entity in [
+            world.spawn(TiledMapLayer).id(),
+            world.spawn(TiledMapTileLayer).id(),
+            world.spawn(TiledMapTileLayerForTileset).id(),
+            world.spawn(TiledMapObjectLayer).id(),
+            world.spawn(TiledMapGroupLayer).id(),
+            world.spawn(TiledMapImageLayer).id(),
+        ] {
+            let entity = world.entity(entity);
+            assert!(entity.contains::<GlobalTransform>());
+            assert!(entity.contains::<InheritedVisibility>());
+            assert!(entity.contains::<ViewVisibility>());
+        }
+    }
+}
@@ -0,0 +1,137 @@
+//! This module contains functions to edit tiles of a spawned map at runtime.
+//!
+//! Tiles are looked up using the [TileStorage] of their tilemap, ie. the
+//! [TiledMapTileLayerForTileset](crate::prelude::TiledMapTileLayerForTileset) [Entity].
+//!
+//! Note that physics colliders are built from the map asset data when a layer is spawned:
+//! they are not updated when tiles are edited.
+
+use bevy::prelude::*;
+use bevy_ecs_tilemap::prelude::*;
+
+use crate::prelude::TiledTileAnimation;
+
+/// Replace the texture and the flip of the tile at given position.
+///
+/// Since the tile texture is explicitly set, any tile animation is removed.
+/// Returns the tile [Entity], or [None] if there is no tile at this position.
+///
+/// Example:
+/// ```rust,no_run
+/// use bevy::prelude::*;
+/// use bevy_ecs_tiled::prelude::*;
+/// use bevy_ecs_tilemap::prelude::*;
+///
+/// fn replace_top_left_tile(mut commands: Commands, tilemap_query: Query<&TileStorage>) {
+///     for storage in tilemap_query.iter() {
+///         let pos = TilePos::new(0, storage.size.y - 1);
+///         set_tile(&mut commands, storage, pos, 0, TileFlip::default());
+///     }
+/// }
+/// ```
+pub fn set_tile(
+    commands: &mut Commands,
+    storage: &TileStorage,
+    pos: TilePos,
+    texture_index: u32,
+    flip: TileFlip,
+) -> Option<Entity> {
+    let tile_entity = storage.checked_get(&pos)?;
+    commands
+        .entity(tile_entity)
+        .insert((TileTextureIndex(texture_index), flip))
+        .remove::<(AnimatedTile, TiledTileAnimation)>();
+    Some(tile_entity)
+}
+
+/// Despawn the tile at given position and remove it from the [TileStorage].
+///
+/// Returns the despawned tile [Entity], or [None] if there is no tile at this position.
+pub fn remove_tile(
+    commands: &mut Commands,
+    storage: &mut TileStorage,
+    pos: TilePos,
+) -> Option<Entity> {
+    let tile_entity = storage.checked_get(&pos)?;
+    storage.remove(&pos);
+    commands.entity(tile_entity).despawn_recursive();
+    Some(tile_entity)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{prelude::*, test_utils::spawn_map};
+
+    /// Find a tilemap along with the position and [Entity] of one of its tiles.
+    fn first_tile(app: &mut App) -> (Entity, TilePos, Entity) {
+        let mut tilemaps = app
+            .world_mut()
+            .query_filtered::<(Entity, &TileStorage), With<TiledMapTileLayerForTileset>>();
+        let (tilemap, storage) = tilemaps.iter(app.world()).next().unwrap();
+        let tile = storage.iter().flatten().next().copied().unwrap();
+        (tilemap, *app.world().get::<TilePos>(tile).unwrap(), tile)
+    }
+
+    #[test]
+    fn set_tile_replaces_texture_and_flip() {
+        let (mut app, _) = spawn_map("maps/orthogonal/finite.tmx", ());
+        let (tilemap, pos, tile) = first_tile(&mut app);
+        app.world_mut().entity_mut(tile).insert((
+            AnimatedTile {
+                start: 0,
+                end: 2,
+                speed: 1.,
+            },
+            TiledTileAnimation::default(),
+        ));
+
+        let storage = app.world().get::<TileStorage>(tilemap).unwrap().clone();
+        let flip = TileFlip {
+            x: true,
+            y: false,
+            d: true,
+        };
+        let edited = set_tile(&mut app.world_mut().commands(), &storage, pos, 42, flip);
+        app.world_mut().flush();
+
+        assert_eq!(edited, Some(tile));
+        let tile = app.world().entity(tile);
+        assert_eq!(tile.get::<TileTextureIndex>(), Some(&TileTextureIndex(42)));
+        assert_eq!(tile.get::<TileFlip>(), Some(&flip));
+        // Texture is explicitly set: animations are removed
+        assert!(!tile.contains::<AnimatedTile>());
+        assert!(!tile.contains::<TiledTileAnimation>());
+    }
+
+    #[test]
+    fn remove_tile_despawns_tile_and_updates_storage() {
+        let (mut app, _) = spawn_map("maps/orthogonal/finite.tmx", ());
+        let (tilemap, pos, tile) = first_tile(&mut app);
+
+        let mut storage =
+            std::mem::take(&mut *app.world_mut().get_mut::<TileStorage>(tilemap).unwrap());
+        let removed = remove_tile(&mut app.world_mut().commands(), &mut storage, pos);
+        app.world_mut().flush();
+
+        assert_eq!(removed, Some(tile));
+        assert!(app.world().get_entity(tile).is_err());
+        assert_eq!(storage.checked_get(&pos), None);
+
+        // There is no more tile to edit at this position
+        assert_eq!(
+            remove_tile(&mut app.world_mut().commands(), &mut storage, pos),
+            None
+        );
+        assert_eq!(
+            set_tile(
+                &mut app.world_mut().commands(),
+                &storage,
+                pos,
+                0,
+                TileFlip::default()
+            ),
+            None
+        );
+    }
+}
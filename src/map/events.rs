@@ -30,6 +30,10 @@ impl fmt::Debug for TiledMapEventWriters<'_> {
 }
 
 /// Event sent when a map is spawned
+///
+/// It is sent (and observers are triggered) once all the map layers, tiles and objects
+/// have been spawned, which makes it a good place to run map initialization logic.
+/// It is the map counterpart of [TiledWorldCreated].
 #[derive(Component, Reflect, Clone, Debug, Copy)]
 #[reflect(Component, Debug)]
 pub struct TiledMapCreated {
@@ -56,6 +60,32 @@ impl<'a> TiledMapCreated {
     }
 }
 
+/// Event sent when a map is about to be unloaded
+///
+/// It is sent right before the map layers, tiles and objects are despawned: either because the
+/// map is respawned (for instance on hot-reload or using [RespawnTiledMap]) or because the map
+/// is despawned following the removal of its [TiledMap] asset or by the world it belongs to.
+/// It is only sent for maps which were actually spawned.
+///
+/// Observers are triggered before the despawn actually happens, so they can still access
+/// the map children entities. It is the counterpart of [TiledMapCreated].
+///
+/// Contrary to [TiledMapDespawned], which is only sent by worlds when they despawn one of
+/// their map [Entity], this event is sent for all maps, whether they belong to a world or not.
+#[derive(Component, Reflect, Clone, Debug, Copy)]
+#[reflect(Component, Debug)]
+pub struct TiledMapUnloaded {
+    /// Unloaded map [Entity]
+    pub entity: Entity,
+    /// [AssetId] of the [TiledMap]
+    pub asset_id: AssetId<TiledMap>,
+}
+
+impl Event for TiledMapUnloaded {
+    type Traversal = &'static Parent;
+    const AUTO_PROPAGATE: bool = true;
+}
+
 /// Event sent when a layer is spawned
 #[derive(Component, Reflect, Clone, Debug, Copy)]
 #[reflect(Component, Debug)]
@@ -65,6 +95,8 @@ pub struct TiledLayerCreated {
     /// Spawned layer [Entity]
     pub entity: Entity,
     /// ID of this layer in the [Map]
+    ///
+    /// Layers nested in group layers are also numbered, see [get_all_layers].
     pub id: usize,
 }
 
@@ -78,7 +110,7 @@ impl<'a> TiledLayerCreated {
     pub fn get_layer(&self, map_asset: &'a Res<Assets<TiledMap>>) -> Option<Layer<'a>> {
         self.map
             .get_map(map_asset)
-            .and_then(|m| m.get_layer(self.id))
+            .and_then(|m| get_layer_from_index(m, self.id))
     }
 }
 
@@ -31,10 +31,19 @@
 #[cfg(feature = "user_properties")]
 use crate::properties::command::PropertiesCommandExt;
 
+use super::{
+    asset::TiledMapTileset,
+    spawner::TiledObjectSpawners,
+    utils::{for_each_tile_in_rows, get_tileset_index},
+    TiledMapSettingsItem,
+};
 use crate::prelude::*;
 use bevy::{prelude::*, sprite::Anchor, utils::HashMap};
 use bevy_ecs_tilemap::prelude::*;
-use tiled::{ImageLayer, Layer, LayerType, ObjectLayer, Tile, TileId, TileLayer, TilesetLocation};
+use std::ops::Range;
+use tiled::{
+    ChunkData, ImageLayer, Layer, LayerType, ObjectLayer, ObjectShape, Tile, TileId, TileLayer,
+};
 
 #[allow(clippy::too_many_arguments)]
 pub(crate) fn load_map(
@@ -43,16 +52,44 @@ pub(crate) fn load_map(
     map_asset_id: AssetId<TiledMap>,
     tiled_map: &TiledMap,
     tiled_id_storage: &mut TiledMapStorage,
-    render_settings: &TilemapRenderSettings,
-    anchor: &TiledMapAnchor,
-    layer_offset: &TiledMapLayerZOffset,
+    map_transform: &GlobalTransform,
+    settings: &TiledMapSettingsItem,
+    object_spawners: &TiledObjectSpawners,
     asset_server: &Res<AssetServer>,
     event_writers: &mut TiledMapEventWriters,
 ) {
-    commands.entity(map_entity).insert((
-        Name::new(format!("TiledMap: {}", tiled_map.map.source.display())),
-        TiledMapMarker,
-    ));
+    let TiledMapSettingsItem {
+        render_settings,
+        anchor,
+        layer_offset,
+        layer_z_fn,
+        layer_visibility_fn,
+        tile_size_override,
+        map_type_override,
+        post_spawn_hook,
+        layer_filter,
+        spawn_rows_per_frame,
+        skip_tile_entities,
+        y_flip,
+    } = *settings;
+    // Map is only marked as spawned once all its entities are: see finish_map_spawn()
+    commands
+        .entity(map_entity)
+        .insert(Name::new(format!(
+            "TiledMap: {}",
+            tiled_map.map.source.display()
+        )))
+        .remove::<TiledMapMarker>();
+    // Map entity is kept when the map is respawned: its class may have been removed
+    match tiled_map
+        .map
+        .user_type
+        .as_deref()
+        .and_then(TiledClass::from_class)
+    {
+        Some(class) => commands.entity(map_entity).insert(class),
+        None => commands.entity(map_entity).remove::<TiledClass>(),
+    };
 
     let map_event = TiledMapCreated {
         entity: map_entity,
@@ -63,34 +100,123 @@ pub(crate) fn load_map(
     let mut object_events: Vec<TiledObjectCreated> = Vec::new();
     let mut special_tile_events: Vec<TiledTileCreated> = Vec::new();
 
+    // Nothing to spawn progressively if we do not spawn tiles at all
+    let spawn_rows_per_frame = spawn_rows_per_frame.filter(|_| !skip_tile_entities);
+
+    // When spawning progressively, tiles are spawned later on by spawn_pending_tiles()
+    let rows = match spawn_rows_per_frame {
+        Some(_) => 0..0,
+        None => 0..tiled_map.tilemap_size.y,
+    };
+    let mut pending_layers: Vec<PendingTilesLayer> = Vec::new();
+
+    // Tilemaps can only be mirrored along the Y axis for orthogonal maps
+    let map_type = map_type_override.map_or_else(|| get_map_type(&tiled_map.map), |o| o.0);
+    let y_flip = y_flip.copied().unwrap_or_default();
+    if !y_flip.0 && y_flip.is_inverted(&map_type) {
+        warn!(
+            "TiledMapYFlip(false) is only supported for orthogonal maps, ignore it (map = {})",
+            tiled_map.map.source.display()
+        );
+    }
+    let y_flip = y_flip.is_inverted(&map_type);
+
+    // Flatten layers hierarchy: group layers come right before their children
+    let layers = get_all_layers(&tiled_map.map);
+
     // Order of the differents layers in the .TMX file is important:
     // a layer appearing last in the .TMX should appear above previous layers
     // Start with a negative offset so in the end we end up with the top layer at Z-offset from settings
-    let mut offset_z = tiled_map.map.layers().len() as f32 * (-layer_offset.0);
+    let mut offset_z = layers.len() as f32 * (-layer_offset.0);
 
-    // Compute layer base Transform given provided TiledMapAnchor
-    let layer_transform = Transform::from_translation(tiled_map.offset(anchor));
+    // Compute layer base translation given provided TiledMapAnchor
+    let base_translation = tiled_map.offset(anchor);
+
+    // Informations about each layer needed to spawn its children
+    let mut layers_infos: Vec<LayerInfos> = Vec::with_capacity(layers.len());
 
     // Once materials have been created/added we need to then create the layers.
-    for (layer_id, layer) in tiled_map.map.layers().enumerate() {
+    for (layer_id, (parent, layer)) in layers.into_iter().enumerate() {
         // Increment Z offset and compute layer transform offset
         offset_z += layer_offset.0;
-        let offset_transform = Transform::from_xyz(layer.offset_x, -layer.offset_y, offset_z);
+        let layer_z = layer_z_fn.map_or(offset_z, |f| (f.0)(layer_id, &layer));
+
+        // Layers nested in a group layer are attached to their group layer entity
+        // and their Tiled offset is relative to their group layer
+        let parent_infos = parent.map(|index| layers_infos[index]);
+        let (parent_entity, parent_translation) = parent_infos
+            .map_or((map_entity, Vec3::ZERO), |p| {
+                (p.entity, p.entity_translation)
+            });
+        let origin = parent_infos.map_or(base_translation, |p| p.translation.with_z(0.));
+        let layer_translation = origin + Vec3::new(layer.offset_x, -layer.offset_y, layer_z);
+        let layer_transform = Transform::from_translation(layer_translation);
+
+        // Group layers opacity and tint color also apply to their children
+        let layer_color = multiply_colors(
+            parent_infos.map_or(Color::WHITE, |p| p.color),
+            get_layer_color(&layer),
+        );
+
+        // Group layers parallax factor also apply to their children
+        let (parent_entity_parallax, parent_parallax) =
+            parent_infos.map_or((Vec2::ONE, Vec2::ONE), |p| (p.entity_parallax, p.parallax));
+        let layer_parallax = parent_parallax * Vec2::new(layer.parallax_x, layer.parallax_y);
+
+        // Skip layers which are filtered out, unless they belong to a spawned group layer
+        if !parent_infos.is_some_and(|p| p.spawned)
+            && !layer_filter.is_none_or(|f| f.matches(layer_id, &layer))
+        {
+            layers_infos.push(LayerInfos {
+                entity: parent_entity,
+                entity_translation: parent_translation,
+                translation: layer_translation,
+                color: layer_color,
+                entity_parallax: parent_entity_parallax,
+                parallax: layer_parallax,
+                spawned: false,
+            });
+            continue;
+        }
 
-        // Spawn layer entity and attach it to the map entity
+        // Spawn layer entity and attach it to its parent entity
         let layer_entity = commands
             .spawn((
                 TiledMapLayer,
-                // Apply layer Transform using both layer base Transform and Tiled offset
-                layer_transform * offset_transform,
+                // Apply layer Transform relative to its parent
+                Transform::from_translation(layer_translation - parent_translation),
                 // Determine layer default visibility
-                match &layer.visible {
-                    true => Visibility::Inherited,
-                    false => Visibility::Hidden,
-                },
+                layer_visibility_fn.map_or(
+                    match &layer.visible {
+                        true => Visibility::Inherited,
+                        false => Visibility::Hidden,
+                    },
+                    |f| (f.0)(&layer),
+                ),
             ))
-            .set_parent(map_entity)
+            .set_parent(parent_entity)
             .id();
+        if let Some(class) = layer.user_type.as_deref().and_then(TiledClass::from_class) {
+            commands.entity(layer_entity).insert(class);
+        }
+        if layer_parallax != parent_entity_parallax {
+            commands.entity(layer_entity).insert(TiledParallaxLayer {
+                factor_x: layer.parallax_x,
+                factor_y: layer.parallax_y,
+                inherited_factor: parent_parallax,
+                parent_factor: parent_entity_parallax,
+                origin: (layer_translation - parent_translation).truncate(),
+            });
+        }
+        layers_infos.push(LayerInfos {
+            entity: layer_entity,
+            entity_translation: layer_translation,
+            translation: layer_translation,
+            color: layer_color,
+            entity_parallax: layer_parallax,
+            parallax: layer_parallax,
+            spawned: true,
+        });
 
         let layer_event = TiledLayerCreated {
             map: map_event,
@@ -100,63 +226,257 @@ pub(crate) fn load_map(
 
         match layer.layer_type() {
             LayerType::Tiles(tile_layer) => {
+                let mut stats = tiled_map
+                    .layer_stats
+                    .get(&layer_id)
+                    .copied()
+                    .unwrap_or_default();
+                if !y_flip {
+                    // Stats are computed using inverted Y coordinates: use the same ones as tiles
+                    let max_y = tiled_map.tilemap_size.y as f32 - 1.;
+                    stats.bounding_rect = stats.bounding_rect.map(|rect| {
+                        Rect::new(
+                            rect.min.x,
+                            max_y - rect.max.y,
+                            rect.max.x,
+                            max_y - rect.min.y,
+                        )
+                    });
+                }
                 commands.entity(layer_entity).insert((
                     Name::new(format!("TiledMapTileLayer({})", layer.name)),
                     TiledMapTileLayer,
+                    stats,
                 ));
-                load_tiles_layer(
-                    commands,
-                    tiled_map,
-                    &layer_event,
-                    layer,
-                    tile_layer,
-                    render_settings,
-                    &mut tiled_id_storage.tiles,
-                    &mut special_tile_events,
-                );
+                if let TileLayer::Infinite(_) = tile_layer {
+                    commands
+                        .entity(layer_entity)
+                        .insert(TiledInfiniteLayerOrigin {
+                            tile_x: tiled_map.topleft_chunk.0 * ChunkData::WIDTH as i32,
+                            tile_y: tiled_map.topleft_chunk.1 * ChunkData::HEIGHT as i32,
+                            tiled_y: !y_flip,
+                        });
+                }
+                if !skip_tile_entities {
+                    let tilemaps = load_tiles_layer(
+                        commands,
+                        tiled_map,
+                        &layer_event,
+                        layer,
+                        tile_layer,
+                        layer_color,
+                        rows.clone(),
+                        render_settings,
+                        tile_size_override,
+                        map_type_override,
+                        y_flip,
+                        &mut tiled_id_storage.tiles,
+                        &mut special_tile_events,
+                    );
+                    pending_layers.push(PendingTilesLayer {
+                        layer_event,
+                        color: layer_color,
+                        tilemaps,
+                    });
+                }
             }
             LayerType::Objects(object_layer) => {
                 commands.entity(layer_entity).insert((
                     Name::new(format!("TiledMapObjectLayer({})", layer.name)),
                     TiledMapObjectLayer,
+                    TiledObjectLayerData {
+                        draw_order: tiled_map.get_object_layer_draw_order(layer.id()),
+                        color: object_layer.colour.as_ref().map(from_tiled_color_to_bevy),
+                        object_count: object_layer.object_data().len(),
+                    },
                 ));
                 load_objects_layer(
                     commands,
                     tiled_map,
                     &layer_event,
+                    map_transform,
+                    layer_transform,
+                    tile_size_override.map_or(Vec2::ONE, |o| o.map_scale(&tiled_map.map)),
                     object_layer,
+                    object_spawners,
                     &mut tiled_id_storage.objects,
                     &mut object_events,
                 );
             }
-            LayerType::Group(_group_layer) => {
+            LayerType::Group(_) => {
                 commands.entity(layer_entity).insert((
                     Name::new(format!("TiledMapGroupLayer({})", layer.name)),
                     TiledMapGroupLayer,
+                    TiledGroupLayer {
+                        id: layer.id(),
+                        name: layer.name.clone(),
+                    },
                 ));
-                warn!("Group layers are not yet implemented");
             }
             LayerType::Image(image_layer) => {
                 commands.entity(layer_entity).insert((
                     Name::new(format!("TiledMapImageLayer({})", layer.name)),
                     TiledMapImageLayer,
                 ));
-                load_image_layer(commands, tiled_map, &layer_event, image_layer, asset_server);
+                load_image_layer(
+                    commands,
+                    tiled_map,
+                    &layer_event,
+                    layer_color,
+                    image_layer,
+                    asset_server,
+                );
             }
         };
 
+        #[cfg(feature = "user_properties")]
+        commands.entity(layer_entity).insert_class(&layer.user_type);
+
         tiled_id_storage.layers.insert(layer.id(), layer_entity);
         layer_events.push(layer_event);
     }
 
+    if let Some(rows_per_frame) = spawn_rows_per_frame {
+        commands.entity(map_entity).insert((
+            TiledMapSpawnProgress {
+                spawned_rows: 0,
+                total_rows: tiled_map.tilemap_size.y,
+            },
+            TiledMapPendingSpawn {
+                rows_per_frame: rows_per_frame.0.max(1),
+                y_flip,
+                map_event,
+                post_spawn_hook: post_spawn_hook.cloned(),
+                layers: pending_layers,
+                layer_events,
+                object_events,
+                tile_events: special_tile_events,
+            },
+        ));
+        return;
+    }
+
+    commands
+        .entity(map_entity)
+        .remove::<(TiledMapSpawnProgress, TiledMapPendingSpawn)>();
+    finish_map_spawn(
+        commands,
+        tiled_map,
+        tiled_id_storage,
+        map_event,
+        post_spawn_hook,
+        layer_events,
+        object_events,
+        special_tile_events,
+        event_writers,
+    );
+}
+
+/// Spawn the next rows of tiles of maps which are spawned progressively.
+///
+/// See [TiledMapSpawnRowsPerFrame].
+pub(crate) fn spawn_pending_tiles(
+    mut commands: Commands,
+    maps: Res<Assets<TiledMap>>,
+    mut map_query: Query<(
+        Entity,
+        &TiledMapHandle,
+        &mut TiledMapStorage,
+        &mut TiledMapSpawnProgress,
+        &mut TiledMapPendingSpawn,
+    )>,
+    mut tile_storage_query: Query<&mut TileStorage>,
+    mut event_writers: TiledMapEventWriters,
+) {
+    for (map_entity, map_handle, mut tiled_id_storage, mut progress, mut pending) in
+        map_query.iter_mut()
+    {
+        let Some(tiled_map) = maps.get(&map_handle.0) else {
+            continue;
+        };
+
+        let rows = progress.spawned_rows
+            ..(progress.spawned_rows + pending.rows_per_frame).min(progress.total_rows);
+        let pending = &mut *pending;
+        for pending_layer in &pending.layers {
+            let Some(tiles_layer) =
+                get_layer_from_index(&tiled_map.map, pending_layer.layer_event.id)
+                    .and_then(|layer| layer.as_tile_layer())
+            else {
+                continue;
+            };
+            for &(tileset_index, tilemap_entity) in &pending_layer.tilemaps {
+                let Some(t) = tiled_map.tilesets.get(&tileset_index) else {
+                    continue;
+                };
+                load_tiles(
+                    &mut commands,
+                    tiled_map,
+                    &pending_layer.layer_event,
+                    tilemap_entity,
+                    t,
+                    tileset_index,
+                    &tiles_layer,
+                    pending_layer.color,
+                    rows.clone(),
+                    pending.y_flip,
+                    tile_storage_query
+                        .get_mut(tilemap_entity)
+                        .ok()
+                        .as_deref_mut(),
+                    &mut tiled_id_storage.tiles,
+                    &mut pending.tile_events,
+                );
+            }
+        }
+        progress.spawned_rows = rows.end;
+
+        if progress.spawned_rows >= progress.total_rows {
+            commands
+                .entity(map_entity)
+                .remove::<(TiledMapSpawnProgress, TiledMapPendingSpawn)>();
+            finish_map_spawn(
+                &mut commands,
+                tiled_map,
+                &tiled_id_storage,
+                pending.map_event,
+                pending.post_spawn_hook.as_ref(),
+                std::mem::take(&mut pending.layer_events),
+                std::mem::take(&mut pending.object_events),
+                std::mem::take(&mut pending.tile_events),
+                &mut event_writers,
+            );
+        }
+    }
+}
+
+/// Mark the map as spawned, insert properties, run the post-spawn hook and send events once all
+/// map entities are spawned.
+#[allow(clippy::too_many_arguments)]
+#[cfg_attr(not(feature = "user_properties"), allow(unused_variables))]
+fn finish_map_spawn(
+    commands: &mut Commands,
+    tiled_map: &TiledMap,
+    tiled_id_storage: &TiledMapStorage,
+    map_event: TiledMapCreated,
+    post_spawn_hook: Option<&TiledMapPostSpawnHook>,
+    layer_events: Vec<TiledLayerCreated>,
+    object_events: Vec<TiledObjectCreated>,
+    special_tile_events: Vec<TiledTileCreated>,
+    event_writers: &mut TiledMapEventWriters,
+) {
+    let map_entity = map_event.entity;
+    commands.entity(map_entity).insert(TiledMapMarker);
+
     #[cfg(feature = "user_properties")]
     {
-        let mut props = tiled_map
-            .properties
-            .clone()
-            .hydrate(&tiled_id_storage.objects);
+        let mut props = tiled_map.properties.clone().hydrate(tiled_id_storage);
 
-        commands.entity(map_entity).insert_properties(props.map);
+        commands
+            .entity(map_entity)
+            .insert(props.map_standard)
+            .insert_properties(props.map)
+            .insert_class(&tiled_map.map.user_type);
 
         for (id, &entity) in tiled_id_storage.objects.iter() {
             commands
@@ -180,6 +500,34 @@ pub(crate) fn load_map(
         }
     }
 
+    if let Some(hook) = post_spawn_hook.cloned() {
+        // Run the hook first and only send events afterwards, if the map still exists
+        commands.queue(move |world: &mut World| {
+            (hook.0)(world, map_entity);
+            if !world.entities().contains(map_entity) {
+                error!(
+                    "TiledMapPostSpawnHook despawned the map entity ({map_entity:?}): do not send map events"
+                );
+                return;
+            }
+            world.trigger_targets(map_event, map_entity);
+            world.send_event(map_event);
+            for e in layer_events {
+                world.trigger_targets(e, map_entity);
+                world.send_event(e);
+            }
+            for e in object_events {
+                world.trigger_targets(e, map_entity);
+                world.send_event(e);
+            }
+            for e in special_tile_events {
+                world.trigger_targets(e, map_entity);
+                world.send_event(e);
+            }
+        });
+        return;
+    }
+
     // Send events and trigger observers
     commands.trigger_targets(map_event, map_entity);
     event_writers.map_event.send(map_event);
@@ -197,17 +545,96 @@ pub(crate) fn load_map(
     }
 }
 
+/// Work left to do for a map which is spawned progressively.
+#[derive(Component, Debug)]
+pub(crate) struct TiledMapPendingSpawn {
+    /// Maximum number of rows of tiles to spawn each frame
+    rows_per_frame: u32,
+    /// Whether tiles Y coordinates are inverted, see [TiledMapYFlip]
+    y_flip: bool,
+    /// Event to send once the map is fully spawned
+    map_event: TiledMapCreated,
+    /// Hook to run once the map is fully spawned
+    post_spawn_hook: Option<TiledMapPostSpawnHook>,
+    /// Tiles layers which tiles remain to be spawned
+    layers: Vec<PendingTilesLayer>,
+    /// Layer events to send once the map is fully spawned
+    layer_events: Vec<TiledLayerCreated>,
+    /// Object events to send once the map is fully spawned
+    object_events: Vec<TiledObjectCreated>,
+    /// Tile events to send once the map is fully spawned
+    tile_events: Vec<TiledTileCreated>,
+}
+
+/// A tiles layer of a map which is spawned progressively.
+#[derive(Debug)]
+struct PendingTilesLayer {
+    /// Event of the layer
+    layer_event: TiledLayerCreated,
+    /// Color of the layer, including its parent layers color
+    color: Color,
+    /// Tilemaps entities of this layer, one per tileset, along with their tileset index
+    tilemaps: Vec<(usize, Entity)>,
+}
+
+/// Informations about a layer needed to spawn its children.
+#[derive(Clone, Copy)]
+struct LayerInfos {
+    /// [Entity] children of this layer are attached to: the layer entity itself or,
+    /// if the layer was filtered out, the entity its parent layer children are attached to
+    entity: Entity,
+    /// Translation of `entity` relative to the map
+    entity_translation: Vec3,
+    /// Translation of the layer relative to the map
+    translation: Vec3,
+    /// Color of the layer, including its parent layers color
+    color: Color,
+    /// Parallax factor applied to `entity`
+    entity_parallax: Vec2,
+    /// Parallax factor of the layer, including its parent layers parallax factor
+    parallax: Vec2,
+    /// Whether the layer was spawned or filtered out
+    spawned: bool,
+}
+
+/// Compute the color of a layer from its tint color and opacity.
+fn get_layer_color(layer: &Layer) -> Color {
+    let color = layer
+        .tint_color
+        .as_ref()
+        .map_or(Color::WHITE, from_tiled_color_to_bevy);
+    color.with_alpha(color.alpha() * layer.opacity)
+}
+
+/// Multiply two colors component-wise, as Tiled does when applying a tint.
+fn multiply_colors(a: Color, b: Color) -> Color {
+    let (a, b) = (a.to_srgba(), b.to_srgba());
+    Color::srgba(
+        a.red * b.red,
+        a.green * b.green,
+        a.blue * b.blue,
+        a.alpha * b.alpha,
+    )
+}
+
 #[allow(clippy::too_many_arguments)]
+#[cfg_attr(not(feature = "render"), allow(unused_variables))]
 fn load_tiles_layer(
     commands: &mut Commands,
     tiled_map: &TiledMap,
     layer_event: &TiledLayerCreated,
     layer: Layer,
     tiles_layer: TileLayer,
+    layer_color: Color,
+    rows: Range<u32>,
     _render_settings: &TilemapRenderSettings,
+    tile_size_override: Option<&TiledMapTileSizeOverride>,
+    map_type_override: Option<&TiledMapTypeOverride>,
+    y_flip: bool,
     entity_map: &mut HashMap<(String, TileId), Vec<Entity>>,
     event_list: &mut Vec<TiledTileCreated>,
-) {
+) -> Vec<(usize, Entity)> {
+    let mut tilemaps = Vec::new();
     // The TilemapBundle requires that all tile images come exclusively from a single
     // tiled texture or from a Vec of independent per-tile images. Furthermore, all of
     // the per-tile images must be the same size. Since Tiled allows tiles of mixed
@@ -215,7 +642,10 @@ fn load_tiles_layer(
     // this means we need to load each combination of tileset and layer separately.
     for (tileset_index, tileset) in tiled_map.map.tilesets().iter().enumerate() {
         let Some(t) = tiled_map.tilesets.get(&tileset_index) else {
-            log::warn!("Skipped creating layer with missing tilemap textures.");
+            warn!(
+                "Skipped layer '{}' because tileset '{}' at index {} failed to load (source = {:?})",
+                layer.name, tileset.name, tileset_index, tileset.source
+            );
             continue;
         };
 
@@ -233,22 +663,61 @@ fn load_tiles_layer(
             ))
             .set_parent(layer_event.entity)
             .id();
+        if let Some(class) = tileset
+            .user_type
+            .as_deref()
+            .and_then(TiledTilesetClass::from_class)
+        {
+            commands.entity(layer_for_tileset_entity).insert(class);
+        }
+
+        #[cfg(feature = "user_properties")]
+        commands
+            .entity(layer_for_tileset_entity)
+            .insert_class(&tileset.user_type);
 
-        let _tile_storage = load_tiles(
+        let mut _tile_storage = TileStorage::empty(tiled_map.tilemap_size);
+        load_tiles(
             commands,
             tiled_map,
             layer_event,
             layer_for_tileset_entity,
-            &t.tilemap_texture,
+            t,
             tileset_index,
             &tiles_layer,
+            layer_color,
+            rows.clone(),
+            y_flip,
+            Some(&mut _tile_storage),
             entity_map,
             event_list,
         );
+        tilemaps.push((tileset_index, layer_for_tileset_entity));
 
         #[cfg(feature = "render")]
         {
             let grid_size = get_grid_size(&tiled_map.map);
+            let tile_size = TilemapTileSize {
+                x: tileset.tile_width as f32,
+                y: tileset.tile_height as f32,
+            };
+            // Tileset drawing offset shifts all the tiles from this tileset,
+            // using Tiled referential (ie. Y axis pointing down)
+            let tileset_offset = Vec2::new(tileset.offset_x as f32, -tileset.offset_y as f32);
+            // Tile size is also used to pick tiles from the tileset texture: keep it as is
+            // and scale the whole tilemap instead
+            let scale = tile_size_override
+                .map_or(Vec2::ONE, |o| o.scale(Vec2::new(tile_size.x, tile_size.y)));
+            let mut translation =
+                (Vec2::new(grid_size.x / 2., grid_size.y / 2.) + tileset_offset) * scale;
+            // Without Y inversion, first Tiled row is at TilePos Y = 0: mirror the tilemap
+            // so this row still ends up at the top of the layer
+            let scale = if y_flip {
+                scale
+            } else {
+                translation.y += (tiled_map.tilemap_size.y as f32 - 1.) * grid_size.y * scale.y;
+                Vec2::new(scale.x, -scale.y)
+            };
             commands
                 .entity(layer_for_tileset_entity)
                 .insert(TilemapBundle {
@@ -256,21 +725,21 @@ fn load_tiles_layer(
                     size: tiled_map.tilemap_size,
                     storage: _tile_storage,
                     texture: t.tilemap_texture.clone(),
-                    tile_size: TilemapTileSize {
-                        x: tileset.tile_width as f32,
-                        y: tileset.tile_height as f32,
-                    },
+                    tile_size,
                     spacing: TilemapSpacing {
                         x: tileset.spacing as f32,
                         y: tileset.spacing as f32,
                     },
-                    transform: Transform::from_xyz(grid_size.x / 2., grid_size.y / 2., 0.),
-                    map_type: get_map_type(&tiled_map.map),
+                    transform: Transform::from_translation(translation.extend(0.))
+                        .with_scale(scale.extend(1.)),
+                    map_type: map_type_override
+                        .map_or_else(|| get_map_type(&tiled_map.map), |o| o.0),
                     render_settings: *_render_settings,
                     ..default()
                 });
         }
     }
+    tilemaps
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -279,37 +748,38 @@ fn load_tiles(
     tiled_map: &TiledMap,
     layer_event: &TiledLayerCreated,
     layer_for_tileset_entity: Entity,
-    tilemap_texture: &TilemapTexture,
+    tileset: &TiledMapTileset,
     tileset_index: usize,
     tiles_layer: &TileLayer,
+    layer_color: Color,
+    rows: Range<u32>,
+    y_flip: bool,
+    mut tile_storage: Option<&mut TileStorage>,
     entity_map: &mut HashMap<(String, TileId), Vec<Entity>>,
     event_list: &mut Vec<TiledTileCreated>,
-) -> TileStorage {
-    let tilemap_size = tiled_map.tilemap_size;
-    let mut tile_storage = TileStorage::empty(tilemap_size);
-    for_each_tile(
+) {
+    let texture_index_of = |tile_id: TileId| {
+        tileset.texture_index(tile_id).expect(
+            "The offset into to image vector should have been saved during the initial load.",
+        )
+    };
+    for_each_tile_in_rows(
         tiled_map,
         tiles_layer,
+        rows,
         |layer_tile, layer_tile_data, tile_pos, index| {
+            let tile_pos = if y_flip {
+                tile_pos
+            } else {
+                TilePos::new(tile_pos.x, index.y as u32)
+            };
             let Some(tile) = layer_tile.get_tile() else {
                 return;
             };
             if tileset_index != layer_tile.tileset_index() {
                 return;
             }
-            let texture_index = match tilemap_texture {
-                TilemapTexture::Single(_) => layer_tile.id(),
-                #[cfg(not(feature = "atlas"))]
-                TilemapTexture::Vector(_) => *tiled_map
-                    .tilesets
-                    .get(&tileset_index)
-                    .and_then(|t| t.tile_image_offsets.get(&layer_tile.id()))
-                    .expect(
-                        "The offset into to image vector should have been saved during the initial load.",
-                    ),
-                #[cfg(not(feature = "atlas"))]
-                _ => unreachable!(),
-            };
+            let texture_index = texture_index_of(layer_tile.id());
             let tile_entity = commands
                 .spawn((
                     TileBundle {
@@ -318,7 +788,8 @@ fn load_tiles(
                         texture_index: TileTextureIndex(texture_index),
                         flip: TileFlip {
                             x: layer_tile_data.flip_h,
-                            y: layer_tile_data.flip_v,
+                            // Compensate the mirrored tilemap when Y is not inverted
+                            y: layer_tile_data.flip_v == y_flip,
                             d: layer_tile_data.flip_d,
                         },
                         ..default()
@@ -332,6 +803,28 @@ fn load_tiles(
             // Handle animated tiles
             if let Some(animated_tile) = get_animated_tile(&tile) {
                 commands.entity(tile_entity).insert(animated_tile);
+            } else if let Some(animation_data) = &tile.animation {
+                // Animation not supported by bevy_ecs_tilemap: we handle it ourselves
+                commands.entity(tile_entity).insert(TiledTileAnimation {
+                    frames: animation_data
+                        .iter()
+                        .map(|frame| (texture_index_of(frame.tile_id), frame.duration))
+                        .collect(),
+                    looping: true,
+                    ..default()
+                });
+            }
+
+            // Handle tiles color: layer color is combined with tile color (Tiled does not
+            // support per-instance tile properties, so we use the property from the tileset tile)
+            #[allow(unused_mut)]
+            let mut tile_color = layer_color;
+            #[cfg(feature = "user_properties")]
+            if let Some(tiled::PropertyValue::ColorValue(color)) = tile.properties.get("color") {
+                tile_color = multiply_colors(tile_color, from_tiled_color_to_bevy(color));
+            }
+            if tile_color != Color::WHITE {
+                commands.entity(tile_entity).insert(TileColor(tile_color));
             }
 
             // Handle custom tiles (with user properties)
@@ -355,28 +848,67 @@ fn load_tiles(
                 .or_insert(vec![tile_entity]);
 
             // Add our tile to the bevy_ecs_tilemap::TileStorage
-            tile_storage.set(&tile_pos, tile_entity);
+            if let Some(tile_storage) = tile_storage.as_deref_mut() {
+                tile_storage.set(&tile_pos, tile_entity);
+            }
         },
     );
-    tile_storage
 }
 
+#[allow(clippy::too_many_arguments)]
 fn load_objects_layer(
     commands: &mut Commands,
     tiled_map: &TiledMap,
     layer_event: &TiledLayerCreated,
+    map_transform: &GlobalTransform,
+    layer_transform: Transform,
+    scale: Vec2,
     object_layer: ObjectLayer,
+    object_spawners: &TiledObjectSpawners,
     entity_map: &mut HashMap<u32, Entity>,
     event_list: &mut Vec<TiledObjectCreated>,
 ) {
+    let mut layer_storage = TiledObjectLayerStorage::default();
     for (object_id, object_data) in object_layer.objects().enumerate() {
         let object_position =
             from_tiled_position_to_world_space(tiled_map, Vec2::new(object_data.x, object_data.y));
+        // Follow the tiles scale when their size is overridden
+        let object_transform = Transform::from_translation((object_position * scale).extend(0.))
+            .with_scale(scale.extend(1.));
+
+        // Let user-defined spawners claim the object first
+        let context = TiledObjectSpawnContext {
+            map_entity: layer_event.map.entity,
+            tiled_map,
+            layer_entity: layer_event.entity,
+            transform: object_transform,
+            world_position: map_transform
+                .transform_point(layer_transform.transform_point(object_transform.translation))
+                .truncate(),
+        };
+        if let Some(object_entity) = object_spawners.spawn(commands, &object_data, &context) {
+            entity_map.insert(object_data.id(), object_entity);
+            layer_storage.0.insert(object_data.id(), object_entity);
+            event_list.push(TiledObjectCreated {
+                layer: *layer_event,
+                entity: object_entity,
+                id: object_id,
+            });
+            continue;
+        }
+
         let object_entity = commands
             .spawn((
                 Name::new(format!("Object({})", object_data.name)),
                 TiledMapObject,
-                Transform::from_xyz(object_position.x, object_position.y, 0.),
+                TiledObjectData {
+                    id: object_data.id(),
+                    name: object_data.name.clone(),
+                    class: object_data.user_type.clone(),
+                    visible: object_data.visible,
+                },
+                TiledObjectShape::from_object(&object_data),
+                object_transform,
                 match &object_data.visible {
                     true => Visibility::Inherited,
                     false => Visibility::Hidden,
@@ -384,15 +916,23 @@ fn load_objects_layer(
             ))
             .set_parent(layer_event.entity)
             .id();
+        if let Some(class) = TiledClass::from_class(&object_data.user_type) {
+            commands.entity(object_entity).insert(class);
+        }
 
         let mut sprite = None;
         let mut animation = None;
+        let mut tileset_offset = Vec2::ZERO;
 
         // Handle objects containing tile data: we want to add a Sprite component to the object with the tile image
         if let Some(tile) = object_data.get_tile() {
-            match tile.tileset_location() {
-                TilesetLocation::Map(tileset_index) => {
-                    sprite = tiled_map.tilesets.get(tileset_index).and_then(|t| {
+            match get_tileset_index(&tiled_map.map, tile.tileset_location()) {
+                Some(tileset_index) => {
+                    if let Some(tileset) = tiled_map.map.tilesets().get(tileset_index) {
+                        tileset_offset =
+                            Vec2::new(tileset.offset_x as f32, -tileset.offset_y as f32);
+                    }
+                    sprite = tiled_map.tilesets.get(&tileset_index).and_then(|t| {
                         match &t.tilemap_texture {
                             TilemapTexture::Single(single) => {
                                 t.texture_atlas_layout_handle.as_ref().map(|handle| {
@@ -425,6 +965,12 @@ fn load_objects_layer(
                     });
 
                     // Handle the case of an animated tile
+                    if tile
+                        .get_tile()
+                        .is_some_and(|t| t.animation.is_some() && get_animated_tile(&t).is_none())
+                    {
+                        log::warn!("Animated tile object with non constant frame duration or non-aligned frame tiles is currently not supported");
+                    }
                     animation =
                         tile.get_tile()
                             .and_then(|t| get_animated_tile(&t))
@@ -438,12 +984,41 @@ fn load_objects_layer(
                                 ),
                             });
                 }
-                TilesetLocation::Template(_) => {
-                    log::warn!("Objects from template are not yet supported");
+                None => {
+                    log::warn!(
+                        "Tile object '{}' (ID {}) uses a tileset from its template which is not used by the map: it will not be displayed",
+                        object_data.name,
+                        object_data.id()
+                    );
                 }
             }
         }
 
+        // Apply layer color as a tint on the object sprite
+        if let (Some(sprite), Some(color)) = (sprite.as_mut(), &object_layer.colour) {
+            sprite.color = from_tiled_color_to_bevy(color);
+        }
+
+        // Handle tile objects flip, size, tileset offset and rotation
+        // Note that rotation is only applied to tile objects Transform: for other objects,
+        // it is directly taken into account when computing their shape and colliders
+        if let (Some(sprite), Some(tile_data)) = (sprite.as_mut(), object_data.tile_data()) {
+            sprite.flip_x = tile_data.flip_h;
+            sprite.flip_y = tile_data.flip_v;
+            if let ObjectShape::Rect { width, height } = object_data.shape {
+                if width > 0. && height > 0. {
+                    sprite.custom_size = Some(Vec2::new(width, height));
+                }
+            }
+            commands.entity(object_entity).insert(
+                Transform::from_translation(
+                    ((object_position + tileset_offset) * scale).extend(0.),
+                )
+                .with_rotation(Quat::from_rotation_z(-object_data.rotation.to_radians()))
+                .with_scale(scale.extend(1.)),
+            );
+        }
+
         match (sprite, animation) {
             (Some(sprite), None) => {
                 commands.entity(object_entity).insert(sprite);
@@ -454,19 +1029,68 @@ fn load_objects_layer(
             _ => {}
         }
 
+        // Compute object bounds in map-local space
+        let corners = match &object_data.shape {
+            ObjectShape::Rect { width, height }
+            | ObjectShape::Ellipse { width, height }
+            | ObjectShape::Text { width, height, .. } => {
+                // Tile objects origin is their bottom-left corner instead of their top-left corner
+                let top = if object_data.get_tile().is_some() {
+                    -height
+                } else {
+                    0.
+                };
+                vec![
+                    Vec2::new(0., top),
+                    Vec2::new(*width, top),
+                    Vec2::new(*width, top + height),
+                    Vec2::new(0., top + height),
+                ]
+            }
+            ObjectShape::Polyline { points } | ObjectShape::Polygon { points } => {
+                points.iter().map(|(x, y)| Vec2::new(*x, *y)).collect()
+            }
+            ObjectShape::Point(_, _) => vec![Vec2::ZERO],
+        };
+        // Tiled rotation is clockwise, around object origin
+        let rotation = Vec2::from_angle(object_data.rotation.to_radians());
+        let origin = Vec2::new(object_data.x, object_data.y);
+        let bounds = corners
+            .into_iter()
+            .map(|corner| {
+                let position =
+                    from_tiled_position_to_world_space(tiled_map, origin + rotation.rotate(corner));
+                layer_transform
+                    .transform_point((position * scale).extend(0.))
+                    .truncate()
+            })
+            .fold(
+                Rect {
+                    min: Vec2::MAX,
+                    max: Vec2::MIN,
+                },
+                |rect, p| rect.union_point(p),
+            );
+        commands
+            .entity(object_entity)
+            .insert(TiledObjectBounds(bounds));
+
         entity_map.insert(object_data.id(), object_entity);
+        layer_storage.0.insert(object_data.id(), object_entity);
         event_list.push(TiledObjectCreated {
             layer: *layer_event,
             entity: object_entity,
             id: object_id,
         });
     }
+    commands.entity(layer_event.entity).insert(layer_storage);
 }
 
 fn load_image_layer(
     commands: &mut Commands,
     tiled_map: &TiledMap,
     layer_event: &TiledLayerCreated,
+    layer_color: Color,
     image_layer: ImageLayer,
     asset_server: &Res<AssetServer>,
 ) {
@@ -484,21 +1108,34 @@ fn load_image_layer(
             _ => Vec2::ZERO,
         };
         let image_position = from_tiled_position_to_world_space(tiled_map, image_position);
-        commands
+        let image_entity = commands
             .spawn((
                 Name::new(format!("Image({})", image.source.display())),
                 TiledMapImage,
                 Sprite {
                     image: asset_server.load(image.source.clone()),
                     anchor: Anchor::TopLeft,
+                    color: layer_color,
                     ..default()
                 },
                 Transform::from_xyz(image_position.x, image_position.y, 0.),
             ))
-            .set_parent(layer_event.entity);
+            .set_parent(layer_event.entity)
+            .id();
+
+        if image_layer.repeat_x || image_layer.repeat_y {
+            commands.entity(image_entity).insert(TiledImageLayerRepeat {
+                x: image_layer.repeat_x,
+                y: image_layer.repeat_y,
+            });
+        }
     }
 }
 
+/// Convert a tile animation to a `bevy_ecs_tilemap` [AnimatedTile].
+///
+/// Returns [None] if the tile is not animated or if its animation cannot be represented
+/// as an [AnimatedTile] (ie. frames with different durations or non-consecutive tiles).
 fn get_animated_tile(tile: &Tile) -> Option<AnimatedTile> {
     let Some(animation_data) = &tile.animation else {
         return None;
@@ -510,12 +1147,10 @@ fn get_animated_tile(tile: &Tile) -> Option<AnimatedTile> {
     // Sanity checks: current limitations from bevy_ecs_tilemap
     for frame in animation_data {
         if frame.duration != first_tile.duration {
-            log::warn!("Animated tile with non constant frame duration is currently not supported");
             return None;
         }
         if let Some(id) = previous_tile_id {
             if frame.tile_id != id + 1 {
-                log::warn!("Animated tile with non-aligned frame tiles is currently not supported");
                 return None;
             }
         }
@@ -2,22 +2,37 @@
 
 pub mod asset;
 pub mod components;
+pub mod edit;
 pub mod events;
 pub mod loader;
+pub mod spawner;
 pub mod utils;
+pub mod wang;
 
 /// `bevy_ecs_tiled` map related public exports
 pub mod prelude {
     pub use super::asset::*;
     pub use super::components::*;
+    pub use super::edit::{remove_tile, set_tile};
     pub use super::events::*;
+    pub use super::spawner::{
+        TiledObjectSpawnContext, TiledObjectSpawner, TiledObjectSpawnerAppExt,
+    };
     pub use super::utils::*;
-    pub use super::TiledMapHandle;
+    pub use super::wang::{TiledWangSet, TiledWangSets};
+    pub use super::{find_layer, tiled_map_ready, TiledLayerIndex, TiledMapHandle};
 }
 
 use crate::{cache::TiledResourceCache, prelude::*};
-use bevy::{asset::RecursiveDependencyLoadState, prelude::*};
+use bevy::{
+    asset::{AssetPath, RecursiveDependencyLoadState},
+    ecs::query::{QueryData, QueryFilter},
+    prelude::*,
+    utils::{HashMap, HashSet},
+};
 use bevy_ecs_tilemap::prelude::*;
+use spawner::TiledObjectSpawners;
+use std::collections::BTreeMap;
 
 /// Wrapper around the [Handle] to the `.tmx` file representing the [TiledMap].
 ///
@@ -34,39 +49,191 @@ use bevy_ecs_tilemap::prelude::*;
 )]
 pub struct TiledMapHandle(pub Handle<TiledMap>);
 
+impl TiledMapHandle {
+    /// Load the `.tmx` file at given path and wrap the resulting [Handle].
+    ///
+    /// Example:
+    /// ```rust,no_run
+    /// use bevy::prelude::*;
+    /// use bevy_ecs_tiled::prelude::*;
+    ///
+    /// fn startup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    ///     commands.spawn(TiledMapHandle::from_path("map.tmx", &asset_server));
+    /// }
+    /// ```
+    pub fn from_path(path: impl Into<AssetPath<'static>>, asset_server: &AssetServer) -> Self {
+        Self(asset_server.load(path))
+    }
+}
+
+/// Run condition returning `true` once at least one map has been spawned.
+///
+/// A map is considered spawned once it has a [TiledMapMarker], ie. once all its tiles have been
+/// spawned and its [TiledMapCreated] event sent.
+///
+/// Example:
+/// ```rust,no_run
+/// use bevy::prelude::*;
+/// use bevy_ecs_tiled::prelude::*;
+///
+/// fn my_system() {}
+///
+/// App::new()
+///     .add_systems(Update, my_system.run_if(tiled_map_ready));
+/// ```
+pub fn tiled_map_ready(query: Query<(), With<TiledMapMarker>>) -> bool {
+    !query.is_empty()
+}
+
+/// Find the [Entity] of a map layer using its name.
+///
+/// Group layers and the layers nested in them are also looked up.
+/// If several layers share the same name, returns the first one, in the `.tmx` file order.
+/// Returns [None] if the map is not spawned yet or if it does not have a spawned layer with this name.
+///
+/// Example:
+/// ```rust,no_run
+/// use bevy::prelude::*;
+/// use bevy_ecs_tiled::prelude::*;
+///
+/// fn hide_collision_layer(world: &mut World, map_entity: Entity) {
+///     if let Some(layer_entity) = find_layer(world, map_entity, "Collision") {
+///         world.entity_mut(layer_entity).insert(Visibility::Hidden);
+///     }
+/// }
+/// ```
+pub fn find_layer(world: &World, map_entity: Entity, name: &str) -> Option<Entity> {
+    let map_handle = world.get::<TiledMapHandle>(map_entity)?;
+    let storage = world.get::<TiledMapStorage>(map_entity)?;
+    // Use the index when it references a layer of this map: it is not up to date until
+    // PostUpdate and only indexes the layers of one map when the same map is spawned several times
+    if let Some(layer_entity) = world
+        .get_resource::<TiledLayerIndex>()
+        .and_then(|index| index.get(map_handle.0.id(), name))
+        .filter(|entity| storage.layers.values().any(|e| e == entity))
+    {
+        return Some(layer_entity);
+    }
+    let tiled_map = world.resource::<Assets<TiledMap>>().get(&map_handle.0)?;
+    get_all_layers(&tiled_map.map)
+        .into_iter()
+        .filter(|(_, layer)| layer.name == name)
+        .find_map(|(_, layer)| storage.layers.get(&layer.id()).copied())
+}
+
+/// [Resource] indexing spawned maps layers entities using their map [AssetId] and their name.
+///
+/// Kept up to date in [PostUpdate] as maps are spawned, respawned or despawned.
+/// If the same map is spawned several times, only the layers of one of these maps are indexed:
+/// use [find_layer] instead to look for the layers of a given map [Entity].
+/// If several layers of a map share the same name, only one of them is indexed.
+#[derive(Resource, Default, Clone, Debug)]
+pub struct TiledLayerIndex(pub HashMap<(AssetId<TiledMap>, String), Entity>);
+
+impl TiledLayerIndex {
+    /// Retrieve a layer [Entity] using its map [AssetId] and its name.
+    pub fn get(&self, map_asset_id: AssetId<TiledMap>, name: &str) -> Option<Entity> {
+        self.0.get(&(map_asset_id, name.to_string())).copied()
+    }
+}
+
 pub(crate) fn build(app: &mut bevy::prelude::App) {
     app.init_asset::<TiledMap>()
         .init_asset_loader::<TiledMapLoader>()
         .register_type::<TiledMapHandle>()
+        .init_resource::<TiledObjectSpawners>()
+        .init_resource::<TiledLayerIndex>()
+        .init_resource::<TiledWangSets>()
         .register_type::<TiledMapPluginConfig>()
         .register_type::<TiledMapAnchor>()
         .register_type::<TiledMapLayerZOffset>()
+        .register_type::<TiledMapTileSizeOverride>()
+        .register_type::<TiledMapTypeOverride>()
+        .register_type::<TiledMapLayerFilter>()
+        .register_type::<TiledMapSpawnRowsPerFrame>()
+        .register_type::<TiledMapSkipTileEntities>()
+        .register_type::<TiledMapYFlip>()
+        .register_type::<TiledMapSpawnProgress>()
         .register_type::<RespawnTiledMap>()
+        .register_type::<TiledMapId>()
         .register_type::<TiledMapStorage>()
         .register_type::<TiledMapMarker>()
+        .register_type::<TiledClass>()
+        .register_type::<TiledTilesetClass>()
+        .register_type::<TiledMapBounds>()
         .register_type::<TiledMapLayer>()
         .register_type::<TiledMapTileLayer>()
+        .register_type::<TiledMapLayerStats>()
+        .register_type::<TiledInfiniteLayerOrigin>()
         .register_type::<TiledMapTileLayerForTileset>()
         .register_type::<TiledMapObjectLayer>()
+        .register_type::<TiledObjectLayerData>()
+        .register_type::<TiledObjectLayerStorage>()
+        .register_type::<TiledGroupLayer>()
         .register_type::<TiledMapImageLayer>()
         .register_type::<TiledMapTile>()
         .register_type::<TiledMapObject>()
+        .register_type::<TiledObjectData>()
+        .register_type::<TiledObjectShape>()
+        .register_type::<TiledObjectBounds>()
         .register_type::<TiledMapImage>()
+        .register_type::<TiledImageLayerRepeat>()
+        .register_type::<TiledParallaxLayer>()
         .register_type::<TiledAnimation>()
+        .register_type::<TiledTileAnimation>()
         .add_event::<TiledMapCreated>()
         .register_type::<TiledMapCreated>()
+        .add_event::<TiledMapUnloaded>()
+        .register_type::<TiledMapUnloaded>()
         .add_event::<TiledLayerCreated>()
         .register_type::<TiledLayerCreated>()
         .add_event::<TiledObjectCreated>()
         .register_type::<TiledObjectCreated>()
         .add_event::<TiledTileCreated>()
         .register_type::<TiledTileCreated>()
-        .add_systems(PreUpdate, process_loaded_maps)
-        .add_systems(Update, animate_tiled_sprites)
-        .add_systems(PostUpdate, handle_map_events);
+        .add_systems(
+            PreUpdate,
+            (
+                (
+                    respawn_on_removed_setting::<TiledMapLayerZFn>,
+                    respawn_on_removed_setting::<TiledMapLayerVisibilityFn>,
+                    respawn_on_removed_setting::<TiledMapTileSizeOverride>,
+                    respawn_on_removed_setting::<TiledMapTypeOverride>,
+                    respawn_on_removed_setting::<TiledMapLayerFilter>,
+                    respawn_on_removed_setting::<TiledMapSpawnRowsPerFrame>,
+                    respawn_on_removed_setting::<TiledMapSkipTileEntities>,
+                    respawn_on_removed_setting::<TiledMapYFlip>,
+                ),
+                process_loaded_maps,
+                loader::spawn_pending_tiles,
+            )
+                .chain(),
+        )
+        .add_systems(Update, (animate_tiled_sprites, animate_tiled_tiles))
+        .add_systems(
+            PostUpdate,
+            (
+                handle_map_events,
+                sync_removed_tiles,
+                update_layer_index,
+                wang::update_wang_sets,
+            ),
+        )
+        .add_systems(
+            PostUpdate,
+            update_parallax_layers.before(TransformSystem::TransformPropagate),
+        )
+        .add_systems(
+            PostUpdate,
+            update_map_bounds.after(TransformSystem::TransformPropagate),
+        );
 
     #[cfg(feature = "user_properties")]
-    app.add_systems(Startup, export_types);
+    app.register_type::<TiledMapProperties>()
+        // Make sure the types used by TiledMapProperties values are registered
+        .register_type::<Color>()
+        .register_type::<Option<Entity>>()
+        .add_systems(Startup, export_types);
 }
 
 #[cfg(feature = "user_properties")]
@@ -82,6 +249,107 @@ fn export_types(reg: Res<AppTypeRegistry>, config: Res<TiledMapPluginConfig>) {
     }
 }
 
+/// Settings components read when spawning a map.
+///
+/// They live on the map [Entity] or on the world [Entity], which forwards them to the maps it
+/// spawns (see [insert_into](TiledMapSettingsItem::insert_into)).
+#[derive(QueryData)]
+pub(crate) struct TiledMapSettings {
+    pub(crate) render_settings: &'static TilemapRenderSettings,
+    pub(crate) anchor: &'static TiledMapAnchor,
+    pub(crate) layer_offset: &'static TiledMapLayerZOffset,
+    pub(crate) layer_z_fn: Option<&'static TiledMapLayerZFn>,
+    pub(crate) layer_visibility_fn: Option<&'static TiledMapLayerVisibilityFn>,
+    pub(crate) tile_size_override: Option<&'static TiledMapTileSizeOverride>,
+    pub(crate) map_type_override: Option<&'static TiledMapTypeOverride>,
+    pub(crate) post_spawn_hook: Option<&'static TiledMapPostSpawnHook>,
+    pub(crate) layer_filter: Option<&'static TiledMapLayerFilter>,
+    pub(crate) spawn_rows_per_frame: Option<&'static TiledMapSpawnRowsPerFrame>,
+    pub(crate) skip_tile_entities: Has<TiledMapSkipTileEntities>,
+    pub(crate) y_flip: Option<&'static TiledMapYFlip>,
+}
+
+impl TiledMapSettingsItem<'_> {
+    /// Insert these settings on a map [Entity] spawned by a world.
+    ///
+    /// [TiledMapAnchor] is not forwarded: the world handles it and maps always use
+    /// [TiledMapAnchor::BottomLeft].
+    pub(crate) fn insert_into(&self, entity: &mut EntityCommands) {
+        entity.insert((
+            TiledMapAnchor::BottomLeft,
+            *self.layer_offset,
+            *self.render_settings,
+        ));
+        if let Some(layer_z_fn) = self.layer_z_fn {
+            entity.insert(layer_z_fn.clone());
+        }
+        if let Some(layer_visibility_fn) = self.layer_visibility_fn {
+            entity.insert(layer_visibility_fn.clone());
+        }
+        if let Some(tile_size_override) = self.tile_size_override {
+            entity.insert(*tile_size_override);
+        }
+        if let Some(map_type_override) = self.map_type_override {
+            entity.insert(*map_type_override);
+        }
+        if let Some(post_spawn_hook) = self.post_spawn_hook {
+            entity.insert(post_spawn_hook.clone());
+        }
+        if let Some(layer_filter) = self.layer_filter {
+            entity.insert(layer_filter.clone());
+        }
+        if let Some(spawn_rows_per_frame) = self.spawn_rows_per_frame {
+            entity.insert(*spawn_rows_per_frame);
+        }
+        if self.skip_tile_entities {
+            entity.insert(TiledMapSkipTileEntities);
+        }
+        if let Some(y_flip) = self.y_flip {
+            entity.insert(*y_flip);
+        }
+    }
+}
+
+/// Filter matching maps or worlds whose [TiledMapSettings] changed.
+///
+/// Changing [TiledMapPostSpawnHook] does not trigger a respawn: it is only used for maps spawned afterwards.
+/// Removed settings are handled by [respawn_on_removed_setting].
+#[derive(QueryFilter)]
+pub(crate) struct TiledMapSettingsChanged {
+    _changed: Or<(
+        Changed<TilemapRenderSettings>,
+        Changed<TiledMapAnchor>,
+        Changed<TiledMapLayerZOffset>,
+        Changed<TiledMapLayerZFn>,
+        Changed<TiledMapLayerVisibilityFn>,
+        Changed<TiledMapTileSizeOverride>,
+        Changed<TiledMapTypeOverride>,
+        Changed<TiledMapLayerFilter>,
+        Changed<TiledMapSpawnRowsPerFrame>,
+        Changed<TiledMapSkipTileEntities>,
+        Changed<TiledMapYFlip>,
+    )>,
+}
+
+/// Respawn maps and worlds when one of their optional [TiledMapSettings] is removed.
+pub(crate) fn respawn_on_removed_setting<T: Component>(
+    mut commands: Commands,
+    mut removed: RemovedComponents<T>,
+    query: Query<(Has<TiledMapHandle>, Has<TiledWorldHandle>)>,
+) {
+    for entity in removed.read() {
+        match query.get(entity) {
+            Ok((true, _)) => {
+                commands.entity(entity).insert(RespawnTiledMap);
+            }
+            Ok((_, true)) => {
+                commands.entity(entity).insert(RespawnTiledWorld);
+            }
+            _ => {}
+        }
+    }
+}
+
 /// System to spawn a map once it has been fully loaded.
 #[allow(clippy::type_complexity)]
 pub(crate) fn process_loaded_maps(
@@ -92,23 +360,31 @@ pub(crate) fn process_loaded_maps(
         (
             Entity,
             &TiledMapHandle,
+            Has<TiledMapMarker>,
             &mut TiledMapStorage,
-            &TilemapRenderSettings,
-            &TiledMapAnchor,
-            &TiledMapLayerZOffset,
+            &Transform,
+            Option<&Parent>,
+            TiledMapSettings,
         ),
         Or<(
             Changed<TiledMapHandle>,
-            Changed<TiledMapAnchor>,
-            Changed<TiledMapLayerZOffset>,
-            Changed<TilemapRenderSettings>,
+            TiledMapSettingsChanged,
             With<RespawnTiledMap>,
         )>,
     >,
+    parent_query: Query<&GlobalTransform>,
+    object_spawners: Res<TiledObjectSpawners>,
     mut event_writers: TiledMapEventWriters,
 ) {
-    for (map_entity, map_handle, mut tiled_id_storage, render_settings, anchor, layer_offset) in
-        map_query.iter_mut()
+    for (
+        map_entity,
+        map_handle,
+        is_spawned,
+        mut tiled_id_storage,
+        map_transform,
+        map_parent,
+        settings,
+    ) in map_query.iter_mut()
     {
         if let Some(load_state) = asset_server.get_recursive_dependency_load_state(&map_handle.0) {
             if !load_state.is_loaded() {
@@ -141,16 +417,25 @@ pub(crate) fn process_loaded_maps(
             );
 
             // Clean previous map layers before trying to spawn the new ones
+            if is_spawned {
+                send_map_unloaded(&mut commands, map_entity);
+            }
             remove_layers(&mut commands, &mut tiled_id_storage);
+            // Map GlobalTransform is not propagated yet if the map was just spawned (ie. by a world)
+            let map_transform = map_parent
+                .and_then(|parent| parent_query.get(parent.get()).ok())
+                .map_or(GlobalTransform::from(*map_transform), |parent_transform| {
+                    parent_transform.mul_transform(*map_transform)
+                });
             loader::load_map(
                 &mut commands,
                 map_entity,
                 map_handle.0.id(),
                 tiled_map,
                 &mut tiled_id_storage,
-                render_settings,
-                anchor,
-                layer_offset,
+                &map_transform,
+                &settings,
+                &object_spawners,
                 &asset_server,
                 &mut event_writers,
             );
@@ -187,6 +472,7 @@ fn handle_map_events(
                 info!("Map removed: {id}");
                 for (map_entity, map_handle) in map_query.iter() {
                     if map_handle.0.id() == *id {
+                        send_map_unloaded(&mut commands, map_entity);
                         commands.entity(map_entity).despawn_recursive();
                     }
                 }
@@ -196,15 +482,195 @@ fn handle_map_events(
     }
 }
 
+/// Send a [TiledMapUnloaded] event for a map which is about to be despawned or respawned.
+///
+/// The event is sent when the command is applied, and only if the map was actually spawned.
+pub(crate) fn send_map_unloaded(commands: &mut Commands, map_entity: Entity) {
+    commands.queue(move |world: &mut World| {
+        let Ok(entity) = world.get_entity(map_entity) else {
+            return;
+        };
+        if !entity.contains::<TiledMapMarker>() {
+            return;
+        }
+        let Some(map_handle) = entity.get::<TiledMapHandle>() else {
+            return;
+        };
+        let event = TiledMapUnloaded {
+            entity: map_entity,
+            asset_id: map_handle.0.id(),
+        };
+        world.trigger_targets(event, map_entity);
+        world.send_event(event);
+    });
+}
+
 fn remove_layers(commands: &mut Commands, tiled_id_storage: &mut TiledMapStorage) {
-    for layer_entity in tiled_id_storage.layers.values() {
-        commands.entity(*layer_entity).despawn_recursive();
+    for &layer_entity in tiled_id_storage.layers.values() {
+        // Layers nested in a group layer may already have been despawned along with their parent
+        commands.queue(move |world: &mut World| {
+            if let Ok(entity) = world.get_entity_mut(layer_entity) {
+                entity.despawn_recursive();
+            }
+        });
     }
     tiled_id_storage.layers.clear();
     tiled_id_storage.objects.clear();
     tiled_id_storage.tiles.clear();
 }
 
+fn update_layer_index(
+    mut layer_index: ResMut<TiledLayerIndex>,
+    // Layers indexed for each map, to only rebuild the index when they actually change
+    mut indexed_layers: Local<HashMap<Entity, (AssetId<TiledMap>, BTreeMap<u32, Entity>)>>,
+    maps: Res<Assets<TiledMap>>,
+    map_query: Query<(Entity, &TiledMapHandle, Ref<TiledMapStorage>)>,
+    mut removed: RemovedComponents<TiledMapStorage>,
+) {
+    let mut rebuild = false;
+    for map_entity in removed.read() {
+        rebuild |= indexed_layers.remove(&map_entity).is_some();
+    }
+    // Storage also changes when tiles or objects are added or removed
+    for (map_entity, map_handle, storage) in map_query.iter() {
+        if !storage.is_changed() {
+            continue;
+        }
+        let map_id = map_handle.0.id();
+        if indexed_layers
+            .get(&map_entity)
+            .is_none_or(|(id, layers)| *id != map_id || *layers != storage.layers)
+        {
+            indexed_layers.insert(map_entity, (map_id, storage.layers.clone()));
+            rebuild = true;
+        }
+    }
+    if !rebuild {
+        return;
+    }
+
+    // Rebuild the whole index: maps do not have that many layers
+    layer_index.0.clear();
+    for (_, map_handle, storage) in map_query.iter() {
+        let Some(tiled_map) = maps.get(&map_handle.0) else {
+            continue;
+        };
+        // Iterate in reverse order so the first layer with a given name wins
+        for (_, layer) in get_all_layers(&tiled_map.map).into_iter().rev() {
+            if let Some(&layer_entity) = storage.layers.get(&layer.id()) {
+                layer_index
+                    .0
+                    .insert((map_handle.0.id(), layer.name.clone()), layer_entity);
+            }
+        }
+    }
+}
+
+/// Remove despawned tiles from the [TiledMapStorage].
+///
+/// This way, tiles can be despawned directly without leaving dangling entities behind.
+/// Note that tiles are removed from their tilemap [TileStorage] as soon as they are despawned,
+/// see [TiledMapTile].
+fn sync_removed_tiles(
+    mut removed: RemovedComponents<TiledMapTile>,
+    mut map_query: Query<&mut TiledMapStorage>,
+) {
+    let removed = removed.read().collect::<HashSet<_>>();
+    if removed.is_empty() {
+        return;
+    }
+
+    for mut map_storage in map_query.iter_mut() {
+        if !map_storage
+            .tiles
+            .values()
+            .flatten()
+            .any(|entity| removed.contains(entity))
+        {
+            continue;
+        }
+        map_storage.tiles.retain(|_, entities| {
+            entities.retain(|entity| !removed.contains(entity));
+            !entities.is_empty()
+        });
+    }
+}
+
+/// Offset layers with a [TiledParallaxLayer] according to the camera position.
+///
+/// As for world chunking, only cameras with the [TiledWorldChunkingCamera] marker are used if any.
+/// If several cameras can be used, the one with the lowest [Camera::order] is picked (then the
+/// lowest [Entity] to break ties). Camera position is read from its [GlobalTransform], so
+/// cameras nested below another entity are supported.
+fn update_parallax_layers(
+    camera_query: Query<(
+        Entity,
+        &Camera,
+        &GlobalTransform,
+        Has<TiledWorldChunkingCamera>,
+    )>,
+    map_query: Query<&GlobalTransform, With<TiledMapMarker>>,
+    parent_query: Query<&Parent>,
+    mut layer_query: Query<
+        (Entity, &TiledParallaxLayer, &mut Transform),
+        (With<TiledMapLayer>, Without<Camera>),
+    >,
+) {
+    let use_marked_cameras = camera_query.iter().any(|(.., is_marked)| is_marked);
+    let Some((_, _, camera_transform, _)) = camera_query
+        .iter()
+        .filter(|(.., is_marked)| !use_marked_cameras || *is_marked)
+        .min_by_key(|(entity, camera, ..)| (camera.order, *entity))
+    else {
+        return;
+    };
+
+    for (entity, parallax, mut transform) in layer_query.iter_mut() {
+        let Some(map_transform) = parent_query
+            .iter_ancestors(entity)
+            .find_map(|ancestor| map_query.get(ancestor).ok())
+        else {
+            continue;
+        };
+        // Camera position relative to the map origin
+        let camera_pos = map_transform
+            .affine()
+            .inverse()
+            .transform_point3(camera_transform.translation())
+            .truncate();
+        let translation = parallax.origin + parallax.offset(camera_pos);
+        if transform.translation.truncate() != translation {
+            transform.translation.x = translation.x;
+            transform.translation.y = translation.y;
+        }
+    }
+}
+
+fn update_map_bounds(
+    mut commands: Commands,
+    maps: Res<Assets<TiledMap>>,
+    map_query: Query<
+        (Entity, &TiledMapHandle, &GlobalTransform, &TiledMapAnchor),
+        (
+            With<TiledMapMarker>,
+            Or<(
+                Changed<TiledMapMarker>,
+                Changed<GlobalTransform>,
+                Changed<TiledMapAnchor>,
+            )>,
+        ),
+    >,
+) {
+    for (map_entity, map_handle, map_transform, anchor) in map_query.iter() {
+        let Some(tiled_map) = maps.get(&map_handle.0) else {
+            continue;
+        };
+        commands.entity(map_entity).insert(TiledMapBounds {
+            aabb: tiled_map.map_aabb(map_transform, anchor),
+        });
+    }
+}
+
 fn animate_tiled_sprites(
     time: Res<Time>,
     mut sprite_query: Query<(&mut TiledAnimation, &mut Sprite)>,
@@ -222,3 +688,517 @@ fn animate_tiled_sprites(
         }
     }
 }
+
+fn animate_tiled_tiles(
+    time: Res<Time>,
+    mut tile_query: Query<(&mut TiledTileAnimation, &mut TileTextureIndex)>,
+) {
+    for (mut animation, mut texture_index) in tile_query.iter_mut() {
+        if animation.frames.is_empty() {
+            continue;
+        }
+        animation.elapsed += time.delta();
+        let frame = animation.frame_at(animation.elapsed);
+        if frame != animation.current_frame || texture_index.0 != animation.frames[frame].0 {
+            animation.current_frame = frame;
+            texture_index.0 = animation.frames[frame].0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::spawn_map;
+
+    #[test]
+    fn parallax_layers() {
+        let (mut app, map_entity) = spawn_map("maps/orthogonal/parallax.tmx", ());
+        let layer = |app: &App, name: &str| {
+            find_layer(app.world(), map_entity, name).expect("layer should be spawned")
+        };
+        let (background, group, nested, foreground) = (
+            layer(&app, "Background"),
+            layer(&app, "Group"),
+            layer(&app, "Nested"),
+            layer(&app, "Foreground"),
+        );
+
+        let parallax = |app: &App, entity| app.world().get::<TiledParallaxLayer>(entity).copied();
+        assert_eq!(
+            parallax(&app, background).map(|p| (p.factor_x, p.factor_y)),
+            Some((0.5, 0.))
+        );
+        assert_eq!(
+            parallax(&app, group).map(|p| (p.factor_x, p.factor_y)),
+            Some((0.5, 0.5))
+        );
+        // Nested layer is only offset through its group layer
+        assert_eq!(parallax(&app, nested), None);
+        assert_eq!(parallax(&app, foreground), None);
+
+        let translation =
+            |app: &App, entity| app.world().get::<Transform>(entity).unwrap().translation;
+        let before = [background, group, nested, foreground].map(|e| translation(&app, e));
+        // Camera position is read from its GlobalTransform
+        let rig = app
+            .world_mut()
+            .spawn(Transform::from_xyz(100., 0., 0.))
+            .id();
+        app.world_mut()
+            .spawn((Camera::default(), Transform::from_xyz(0., 40., 0.)))
+            .set_parent(rig);
+        // Camera with the lowest order is used
+        app.world_mut().spawn((
+            Camera {
+                order: 1,
+                ..default()
+            },
+            Transform::from_xyz(-500., -500., 0.),
+        ));
+        // Camera GlobalTransform is only propagated at the end of the first update
+        app.update();
+        app.update();
+        let after = [background, group, nested, foreground].map(|e| translation(&app, e));
+
+        assert_eq!(after[0] - before[0], Vec3::new(50., 40., 0.));
+        assert_eq!(after[1] - before[1], Vec3::new(50., 20., 0.));
+        assert_eq!(after[2], before[2]);
+        assert_eq!(after[3], before[3]);
+    }
+
+    #[test]
+    fn object_bounds() {
+        let (app, map_entity) = spawn_map("maps/orthogonal/finite.tmx", ());
+        let storage = app.world().get::<TiledMapStorage>(map_entity).unwrap();
+        assert!(!storage.objects.is_empty());
+        for entity in storage.objects.values() {
+            let bounds = app.world().get::<TiledObjectBounds>(*entity).unwrap().0;
+            assert!(bounds.min.is_finite() && bounds.max.is_finite());
+            assert!(bounds.min.cmple(bounds.max).all());
+        }
+
+        // First object is an unrotated 319x29 rectangle
+        let bounds = app
+            .world()
+            .get::<TiledObjectBounds>(storage.objects[&1])
+            .unwrap()
+            .0;
+        assert_eq!(bounds.size(), Vec2::new(319., 29.));
+    }
+
+    #[test]
+    fn map_unloaded_event() {
+        let (mut app, map_entity) = spawn_map("maps/orthogonal/finite.tmx", ());
+        let unloaded = |app: &App| {
+            let events = app.world().resource::<Events<TiledMapUnloaded>>();
+            events
+                .iter_current_update_events()
+                .map(|e| e.entity)
+                .collect::<Vec<_>>()
+        };
+
+        app.world_mut()
+            .entity_mut(map_entity)
+            .insert(RespawnTiledMap);
+        app.update();
+        assert_eq!(unloaded(&app), vec![map_entity]);
+
+        // Same as what a world does when despawning one of its maps
+        send_map_unloaded(&mut app.world_mut().commands(), map_entity);
+        app.world_mut()
+            .commands()
+            .entity(map_entity)
+            .despawn_recursive();
+        app.world_mut().flush();
+        assert_eq!(unloaded(&app), vec![map_entity, map_entity]);
+
+        // Nothing is sent for a map which is not spawned
+        let pending = app.world_mut().spawn_empty().id();
+        send_map_unloaded(&mut app.world_mut().commands(), pending);
+        app.world_mut().flush();
+        assert_eq!(unloaded(&app).len(), 2);
+    }
+
+    #[test]
+    fn map_marked_once_spawned_progressively() {
+        let (app, map_entity) =
+            spawn_map("maps/orthogonal/finite.tmx", TiledMapSpawnRowsPerFrame(1));
+        // spawn_map() returns one frame after the marker is inserted: all rows must be spawned
+        assert!(!app
+            .world()
+            .entity(map_entity)
+            .contains::<TiledMapSpawnProgress>());
+        let storage = app.world().get::<TiledMapStorage>(map_entity).unwrap();
+        assert!(!storage.tiles.is_empty());
+        assert!(!app.world().resource::<Events<TiledMapCreated>>().is_empty());
+    }
+
+    #[test]
+    fn linear_layer_z_fn_matches_layer_z_offset() {
+        let layers_z = |app: &App, map_entity| {
+            let storage = app.world().get::<TiledMapStorage>(map_entity).unwrap();
+            storage
+                .layers
+                .values()
+                .map(|e| app.world().get::<Transform>(*e).unwrap().translation.z)
+                .collect::<Vec<_>>()
+        };
+        let (app, map_entity) = spawn_map("maps/orthogonal/finite.tmx", TiledMapLayerZOffset(10.));
+        let expected = layers_z(&app, map_entity);
+        let (app, map_entity) = spawn_map(
+            "maps/orthogonal/finite.tmx",
+            (TiledMapLayerZOffset(10.), TiledMapLayerZFn::linear(10.)),
+        );
+        assert_eq!(layers_z(&app, map_entity), expected);
+        assert_eq!(expected.last(), Some(&0.));
+    }
+
+    #[test]
+    fn post_spawn_hook_despawning_map() {
+        let (app, map_entity) = spawn_map(
+            "maps/orthogonal/finite.tmx",
+            TiledMapPostSpawnHook::new(|world, map_entity| {
+                world.entity_mut(map_entity).despawn_recursive();
+            }),
+        );
+        assert!(app.world().get_entity(map_entity).is_err());
+        assert!(app.world().resource::<Events<TiledMapCreated>>().is_empty());
+        assert!(app
+            .world()
+            .resource::<Events<TiledLayerCreated>>()
+            .is_empty());
+    }
+
+    #[test]
+    fn despawned_tiles_are_removed_from_storages() {
+        let (mut app, map_entity) = spawn_map("maps/orthogonal/finite.tmx", ());
+        let mut tilemaps = app
+            .world_mut()
+            .query_filtered::<Entity, With<TiledMapTileLayerForTileset>>();
+        let tilemaps = tilemaps.iter(app.world()).collect::<Vec<_>>();
+        assert!(tilemaps.len() > 1);
+        let (tilemap, other) = (tilemaps[0], tilemaps[1]);
+        let (tile_pos, tile) = {
+            let storage = app.world().get::<TileStorage>(tilemap).unwrap();
+            let tile = storage.iter().flatten().next().copied().unwrap();
+            (*app.world().get::<TilePos>(tile).unwrap(), tile)
+        };
+        let last_changed = |app: &App, entity| {
+            app.world()
+                .entity(entity)
+                .get_ref::<TileStorage>()
+                .unwrap()
+                .last_changed()
+        };
+        let other_changed = last_changed(&app, other);
+
+        app.world_mut().entity_mut(tile).despawn_recursive();
+        app.update();
+
+        let storage = app.world().get::<TileStorage>(tilemap).unwrap();
+        assert_eq!(storage.checked_get(&tile_pos), None);
+        // Other tilemaps are left untouched
+        assert_eq!(last_changed(&app, other), other_changed);
+        let map_storage = app.world().get::<TiledMapStorage>(map_entity).unwrap();
+        assert!(!map_storage.tiles.values().flatten().any(|e| *e == tile));
+    }
+
+    #[test]
+    fn tiles_without_y_flip() {
+        let tiles = |app: &App, map_entity| {
+            let storage = app.world().get::<TiledMapStorage>(map_entity).unwrap();
+            storage
+                .tiles
+                .iter()
+                .flat_map(|(key, entities)| entities.iter().map(move |e| (key.clone(), *e)))
+                .map(|(key, e)| {
+                    let pos = app.world().get::<TilePos>(e).unwrap();
+                    let flip = app.world().get::<TileFlip>(e).unwrap();
+                    (key, pos.x, pos.y, flip.y)
+                })
+                .collect::<HashSet<_>>()
+        };
+        let (app, map_entity) = spawn_map("maps/orthogonal/finite.tmx", ());
+        let height = app
+            .world()
+            .resource::<Assets<TiledMap>>()
+            .iter()
+            .next()
+            .unwrap()
+            .1
+            .tilemap_size
+            .y;
+        let flipped = tiles(&app, map_entity);
+        assert!(!flipped.is_empty());
+        let stats = |app: &App, map_entity| {
+            let layer = find_layer(app.world(), map_entity, "Tile Layer 1").unwrap();
+            app.world()
+                .get::<TiledMapLayerStats>(layer)
+                .unwrap()
+                .bounding_rect
+                .unwrap()
+        };
+        let flipped_rect = stats(&app, map_entity);
+        // Stats are computed once when loading the map, using inverted Y coordinates
+        let (_, tiled_map) = app
+            .world()
+            .resource::<Assets<TiledMap>>()
+            .iter()
+            .next()
+            .unwrap();
+        assert_eq!(
+            tiled_map.layer_stats.get(&1).and_then(|s| s.bounding_rect),
+            Some(flipped_rect)
+        );
+
+        let (mut app, map_entity) = spawn_map("maps/orthogonal/finite.tmx", TiledMapYFlip(false));
+        // Tiles use Tiled coordinates and are vertically flipped to compensate the mirrored tilemap
+        let expected = flipped
+            .into_iter()
+            .map(|(key, x, y, flip_y)| (key, x, height - 1 - y, !flip_y))
+            .collect::<HashSet<_>>();
+        assert_eq!(tiles(&app, map_entity), expected);
+        let rect = stats(&app, map_entity);
+        assert_eq!(rect.min.y, height as f32 - 1. - flipped_rect.max.y);
+        assert_eq!(rect.max.y, height as f32 - 1. - flipped_rect.min.y);
+
+        #[cfg(feature = "render")]
+        {
+            let mut query = app.world_mut().query_filtered::<
+                (&TilemapGridSize, &Transform),
+                With<TiledMapTileLayerForTileset>,
+            >();
+            for (grid_size, transform) in query.iter(app.world()) {
+                assert_eq!(transform.scale, Vec3::new(1., -1., 1.));
+                assert_eq!(transform.translation.y, (height as f32 - 0.5) * grid_size.y);
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "render")]
+    fn tile_pos_to_world_matches_spawned_tiles() {
+        for (y_flip, scale) in [(true, 1.), (false, 1.), (true, 2.), (false, 2.)] {
+            let (mut app, _) = spawn_map(
+                "maps/orthogonal/finite.tmx",
+                (
+                    TiledMapYFlip(y_flip),
+                    TiledMapTileSizeOverride(TilemapTileSize {
+                        x: 32. * scale,
+                        y: 32. * scale,
+                    }),
+                ),
+            );
+            let mut query = app.world_mut().query_filtered::<(
+                &TileStorage,
+                &TilemapSize,
+                &TilemapGridSize,
+                &TilemapType,
+                &GlobalTransform,
+            ), With<TiledMapTileLayerForTileset>>();
+            let mut count = 0;
+            for (storage, map_size, grid_size, map_type, transform) in query.iter(app.world()) {
+                for tile in storage.iter().flatten() {
+                    let tile_pos = app.world().get::<TilePos>(*tile).unwrap();
+                    // Tiled coordinates of this tile, then center of its cell in map space
+                    let row = if y_flip {
+                        map_size.y - 1 - tile_pos.y
+                    } else {
+                        tile_pos.y
+                    };
+                    let expected = Vec2::new(
+                        (tile_pos.x as f32 + 0.5) * 32. * scale,
+                        (map_size.y as f32 - row as f32 - 0.5) * 32. * scale,
+                    );
+                    let world = tile_pos_to_world(tile_pos, transform, grid_size, map_type);
+                    assert!((world - expected).length() < 0.001, "{world} != {expected}");
+                    assert_eq!(
+                        world_to_tile_pos(world, transform, grid_size, map_type, map_size),
+                        Some(*tile_pos)
+                    );
+                    count += 1;
+                }
+            }
+            assert!(count > 0);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "render")]
+    fn tile_size_override_scales_tilemaps() {
+        let (mut app, _) = spawn_map(
+            "maps/orthogonal/finite.tmx",
+            TiledMapTileSizeOverride(TilemapTileSize { x: 64., y: 48. }),
+        );
+        let mut query = app.world_mut().query_filtered::<
+            (&TilemapTileSize, &TilemapGridSize, &Transform),
+            With<TiledMapTileLayerForTileset>,
+        >();
+        let mut count = 0;
+        for (tile_size, grid_size, transform) in query.iter(app.world()) {
+            // Tiles are still picked from the texture using the tileset tile size
+            assert_eq!((tile_size.x, tile_size.y), (32., 32.));
+            assert_eq!((grid_size.x, grid_size.y), (32., 32.));
+            assert_eq!(transform.scale, Vec3::new(2., 1.5, 1.));
+            assert_eq!(transform.translation.truncate(), Vec2::new(32., 24.));
+            count += 1;
+        }
+        assert!(count > 0);
+    }
+
+    #[test]
+    fn tile_size_override_scales_objects() {
+        let objects = |app: &mut App| {
+            let mut query = app
+                .world_mut()
+                .query_filtered::<(&TiledObjectData, &Transform), With<TiledMapObject>>();
+            query
+                .iter(app.world())
+                .map(|(data, transform)| (data.id, *transform))
+                .collect::<HashMap<_, _>>()
+        };
+        let (mut app, _) = spawn_map("maps/orthogonal/finite.tmx", ());
+        let unscaled = objects(&mut app);
+        assert!(!unscaled.is_empty());
+
+        let (mut app, _) = spawn_map(
+            "maps/orthogonal/finite.tmx",
+            TiledMapTileSizeOverride(TilemapTileSize { x: 64., y: 48. }),
+        );
+        let scale = Vec3::new(2., 1.5, 1.);
+        for (id, transform) in objects(&mut app) {
+            let expected = unscaled[&id];
+            assert_eq!(transform.translation, expected.translation * scale);
+            assert_eq!(transform.scale, scale);
+        }
+    }
+
+    #[test]
+    fn layer_index_rebuilt_when_layers_change() {
+        let (mut app, map_entity) = spawn_map("maps/orthogonal/finite.tmx", ());
+        let map_id = app
+            .world()
+            .get::<TiledMapHandle>(map_entity)
+            .unwrap()
+            .0
+            .id();
+        let layer = find_layer(app.world(), map_entity, "Tile Layer 1");
+        assert!(layer.is_some());
+        assert_eq!(
+            app.world()
+                .resource::<TiledLayerIndex>()
+                .get(map_id, "Tile Layer 1"),
+            layer
+        );
+
+        // Storage changes which do not affect layers do not rebuild the index
+        let last_changed = |app: &App| {
+            app.world()
+                .get_resource_ref::<TiledLayerIndex>()
+                .unwrap()
+                .last_changed()
+        };
+        let before = last_changed(&app);
+        app.world_mut()
+            .get_mut::<TiledMapStorage>(map_entity)
+            .unwrap()
+            .set_changed();
+        app.update();
+        assert_eq!(last_changed(&app), before);
+
+        app.world_mut().entity_mut(map_entity).despawn_recursive();
+        app.update();
+        assert!(app.world().resource::<TiledLayerIndex>().0.is_empty());
+    }
+
+    #[test]
+    fn tiled_classes() {
+        let (mut app, map_entity) = spawn_map("maps/orthogonal/classes.tmx", ());
+        let class = |app: &App, entity| app.world().get::<TiledClass>(entity).cloned();
+        assert_eq!(class(&app, map_entity), TiledClass::from_class("Level"));
+        let layer = find_layer(app.world(), map_entity, "Tile Layer 1").unwrap();
+        assert_eq!(class(&app, layer), TiledClass::from_class("Ground"));
+        let objects_layer = find_layer(app.world(), map_entity, "Objects").unwrap();
+        assert_eq!(class(&app, objects_layer), None);
+        let storage = app.world().get::<TiledMapStorage>(map_entity).unwrap();
+        assert_eq!(
+            class(&app, storage.objects[&1]),
+            TiledClass::from_class("Door")
+        );
+
+        // Tilesets class does not end up in TiledClass
+        #[cfg(not(feature = "atlas"))]
+        {
+            let mut query = app.world_mut().query_filtered::<
+                (Option<&TiledClass>, Option<&TiledTilesetClass>),
+                With<TiledMapTileLayerForTileset>,
+            >();
+            let classes = query.iter(app.world()).collect::<Vec<_>>();
+            assert!(!classes.is_empty());
+            for (class, tileset_class) in classes {
+                assert_eq!(class, None);
+                assert_eq!(
+                    tileset_class,
+                    TiledTilesetClass::from_class("Terrain").as_ref()
+                );
+            }
+        }
+
+        // Map class is removed when the map is respawned without a class
+        let handle = app
+            .world()
+            .resource::<AssetServer>()
+            .load("maps/orthogonal/finite_embedded.tmx");
+        app.world_mut()
+            .entity_mut(map_entity)
+            .insert(TiledMapHandle(handle));
+        // New map does not have an objects layer (Tiled ID 2)
+        crate::test_utils::update_until(&mut app, "map respawn", |world| {
+            world.get::<TiledMapMarker>(map_entity).is_some()
+                && world
+                    .get::<TiledMapStorage>(map_entity)
+                    .is_some_and(|storage| !storage.layers.contains_key(&2))
+        });
+        assert_eq!(class(&app, map_entity), None);
+    }
+
+    #[test]
+    fn removed_setting_respawns_map() {
+        let (mut app, map_entity) = spawn_map(
+            "maps/orthogonal/finite.tmx",
+            TiledMapLayerFilter::ByName(vec!["Collisions".into()]),
+        );
+        assert!(find_layer(app.world(), map_entity, "Tile Layer 1").is_none());
+
+        app.world_mut()
+            .entity_mut(map_entity)
+            .remove::<TiledMapLayerFilter>();
+        crate::test_utils::update_until(&mut app, "map respawn", |world| {
+            find_layer(world, map_entity, "Tile Layer 1").is_some()
+        });
+    }
+
+    #[test]
+    fn templated_objects() {
+        let (app, map_entity) = spawn_map("maps/orthogonal/templates.tmx", ());
+        let storage = app.world().get::<TiledMapStorage>(map_entity).unwrap();
+        let names = [1, 2, 3].map(|id| {
+            app.world()
+                .get::<TiledObjectData>(storage.objects[&id])
+                .map(|data| data.name.clone())
+        });
+        assert_eq!(
+            names,
+            ["Box", "Box", "Tile"].map(|name| Some(name.to_string()))
+        );
+
+        // Tile object from a template uses the map tileset loaded from the same file
+        #[cfg(not(feature = "atlas"))]
+        {
+            let tile_object = storage.objects[&3];
+            assert!(app.world().get::<Sprite>(tile_object).is_some());
+            assert!(app.world().get::<Sprite>(storage.objects[&1]).is_none());
+        }
+    }
+}
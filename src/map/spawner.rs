@@ -0,0 +1,179 @@
+//! This module contains the [TiledObjectSpawner] extension point.
+
+use bevy::prelude::*;
+use std::{fmt, sync::Arc};
+
+use crate::prelude::TiledMap;
+
+/// User-defined hook to spawn Tiled objects.
+///
+/// When loading an object layer, registered spawners are called in registration order for
+/// each object. The first spawner returning `Some(entity)` claims the object: the default
+/// [TiledMapObject](crate::prelude::TiledMapObject) entity is not spawned and the returned
+/// [Entity] is used instead, for instance to spawn [TiledObjectCreated](crate::prelude::TiledObjectCreated)
+/// events or to insert custom properties. If no spawner claims the object, the default entity is spawned.
+///
+/// Spawners are responsible for attaching the [Entity] they spawn to the layer [Entity] if they want to:
+/// [TiledObjectSpawnContext] provides the [Transform] to use for that, along with the object
+/// position in world space.
+///
+/// Example:
+/// ```rust,no_run
+/// use bevy::prelude::*;
+/// use bevy_ecs_tiled::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Enemy;
+///
+/// #[derive(Default)]
+/// struct EnemySpawner;
+///
+/// impl TiledObjectSpawner for EnemySpawner {
+///     fn spawn(
+///         &self,
+///         commands: &mut Commands,
+///         object: &tiled::Object,
+///         context: &TiledObjectSpawnContext,
+///     ) -> Option<Entity> {
+///         (object.user_type == "Enemy").then(|| {
+///             commands
+///                 .spawn((Enemy, Name::new(object.name.clone()), context.transform))
+///                 .set_parent(context.layer_entity)
+///                 .id()
+///         })
+///     }
+/// }
+///
+/// App::new().add_tiled_object_spawner::<EnemySpawner>();
+/// ```
+pub trait TiledObjectSpawner: Send + Sync + 'static {
+    /// Spawn an [Entity] for the given object, or return [None] to let other spawners handle it.
+    fn spawn(
+        &self,
+        commands: &mut Commands,
+        object: &tiled::Object,
+        context: &TiledObjectSpawnContext,
+    ) -> Option<Entity>;
+}
+
+/// Informations about the object passed to [TiledObjectSpawner::spawn].
+#[derive(Clone, Copy, Debug)]
+pub struct TiledObjectSpawnContext<'a> {
+    /// [Entity] of the map the object belongs to.
+    pub map_entity: Entity,
+    /// [TiledMap] the object belongs to.
+    pub tiled_map: &'a TiledMap,
+    /// [Entity] of the objects layer the object belongs to.
+    pub layer_entity: Entity,
+    /// [Transform] of the object, relative to the layer [Entity].
+    ///
+    /// It is the one used by the default [TiledMapObject](crate::prelude::TiledMapObject) entity
+    /// (except for tile objects, which are also offset and rotated).
+    pub transform: Transform,
+    /// Position of the object in world space.
+    ///
+    /// Layers parallax offset is not taken into account.
+    pub world_position: Vec2,
+}
+
+/// Registered [TiledObjectSpawner]s.
+#[derive(Resource, Default, Clone)]
+pub(crate) struct TiledObjectSpawners(pub(crate) Vec<Arc<dyn TiledObjectSpawner>>);
+
+impl TiledObjectSpawners {
+    /// Call registered spawners until one of them claims the object.
+    pub(crate) fn spawn(
+        &self,
+        commands: &mut Commands,
+        object: &tiled::Object,
+        context: &TiledObjectSpawnContext,
+    ) -> Option<Entity> {
+        self.0
+            .iter()
+            .find_map(|spawner| spawner.spawn(commands, object, context))
+    }
+}
+
+impl fmt::Debug for TiledObjectSpawners {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("TiledObjectSpawners")
+            .field(&self.0.len())
+            .finish()
+    }
+}
+
+/// [App] extension to register [TiledObjectSpawner]s.
+pub trait TiledObjectSpawnerAppExt {
+    /// Register a [TiledObjectSpawner], using its [Default] implementation.
+    fn add_tiled_object_spawner<T: TiledObjectSpawner + Default>(&mut self) -> &mut Self;
+}
+
+impl TiledObjectSpawnerAppExt for App {
+    fn add_tiled_object_spawner<T: TiledObjectSpawner + Default>(&mut self) -> &mut Self {
+        self.world_mut()
+            .get_resource_or_insert_with(TiledObjectSpawners::default)
+            .0
+            .push(Arc::new(T::default()));
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{prelude::*, test_utils::*};
+
+    #[derive(Component)]
+    struct Door {
+        map_entity: Entity,
+        world_position: Vec2,
+    }
+
+    #[derive(Default)]
+    struct DoorSpawner;
+
+    impl TiledObjectSpawner for DoorSpawner {
+        fn spawn(
+            &self,
+            commands: &mut Commands,
+            object: &tiled::Object,
+            context: &TiledObjectSpawnContext,
+        ) -> Option<Entity> {
+            (object.user_type == "Door").then(|| {
+                commands
+                    .spawn((
+                        Door {
+                            map_entity: context.map_entity,
+                            world_position: context.world_position,
+                        },
+                        context.transform,
+                    ))
+                    .set_parent(context.layer_entity)
+                    .id()
+            })
+        }
+    }
+
+    #[test]
+    fn registered_spawner_claims_objects() {
+        let mut app = test_app();
+        app.add_tiled_object_spawner::<DoorSpawner>();
+        let map_entity = spawn_map_in(
+            &mut app,
+            "maps/orthogonal/classes.tmx",
+            Transform::from_xyz(1000., 0., 0.),
+        );
+
+        let storage = app.world().get::<TiledMapStorage>(map_entity).unwrap();
+        let door_entity = storage.objects[&1];
+        assert!(app.world().get::<TiledMapObject>(door_entity).is_none());
+        let door = app.world().get::<Door>(door_entity).unwrap();
+        assert_eq!(door.map_entity, map_entity);
+        // Object is at (64, 96) in Tiled, in a 320 pixels high map
+        assert_eq!(door.world_position, Vec2::new(1064., 224.));
+
+        // Spawner Transform matches the object world position
+        let transform = app.world().get::<GlobalTransform>(door_entity).unwrap();
+        assert_eq!(transform.translation().truncate(), door.world_position);
+    }
+}
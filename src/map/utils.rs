@@ -1,86 +1,210 @@
 //! This module contains utilities functions.
-use bevy::prelude::*;
+use std::{ops::Range, sync::Arc};
+
+use bevy::{math::bounding::Aabb2d, prelude::*};
 use bevy_ecs_tilemap::prelude::*;
-use tiled::{ChunkData, LayerTile, LayerTileData, Map, TileLayer};
+use tiled::{
+    ChunkData, Layer, LayerTile, LayerTileData, LayerType, Map, TileLayer, TilesetLocation,
+};
 
 use super::TiledMap;
 
 /// Convert a [Map]'s [tiled::Orientation] to a [TilemapType]
+///
+/// Isometric "staggered" maps are laid out as hexagonal maps with a zero hexagon side length:
+/// as for hexagonal maps, we use [Map::stagger_axis] and [Map::stagger_index] to pick the
+/// proper [HexCoordSystem].
 pub fn get_map_type(map: &Map) -> TilemapType {
     match map.orientation {
         tiled::Orientation::Orthogonal => TilemapType::Square,
-        tiled::Orientation::Hexagonal => match map.stagger_axis {
-            tiled::StaggerAxis::X if map.stagger_index == tiled::StaggerIndex::Even => {
-                TilemapType::Hexagon(HexCoordSystem::ColumnOdd)
-            }
-            tiled::StaggerAxis::X if map.stagger_index == tiled::StaggerIndex::Odd => {
-                TilemapType::Hexagon(HexCoordSystem::ColumnEven)
-            }
-            tiled::StaggerAxis::Y if map.stagger_index == tiled::StaggerIndex::Even => {
-                TilemapType::Hexagon(HexCoordSystem::RowOdd)
-            }
-            tiled::StaggerAxis::Y if map.stagger_index == tiled::StaggerIndex::Odd => {
-                TilemapType::Hexagon(HexCoordSystem::RowEven)
+        tiled::Orientation::Hexagonal | tiled::Orientation::Staggered => {
+            match (&map.stagger_axis, &map.stagger_index) {
+                (tiled::StaggerAxis::X, tiled::StaggerIndex::Even) => {
+                    TilemapType::Hexagon(HexCoordSystem::ColumnOdd)
+                }
+                (tiled::StaggerAxis::X, tiled::StaggerIndex::Odd) => {
+                    TilemapType::Hexagon(HexCoordSystem::ColumnEven)
+                }
+                (tiled::StaggerAxis::Y, tiled::StaggerIndex::Even) => {
+                    TilemapType::Hexagon(HexCoordSystem::RowOdd)
+                }
+                (tiled::StaggerAxis::Y, tiled::StaggerIndex::Odd) => {
+                    TilemapType::Hexagon(HexCoordSystem::RowEven)
+                }
             }
-            _ => unreachable!(),
-        },
-        tiled::Orientation::Isometric => TilemapType::Isometric(IsoCoordSystem::Diamond),
-        tiled::Orientation::Staggered => {
-            panic!("Isometric (Staggered) map is not supported");
         }
+        tiled::Orientation::Isometric => TilemapType::Isometric(IsoCoordSystem::Diamond),
     }
 }
 
 /// Convert a [Map]'s grid size to a [TilemapGridSize]
+///
+/// For hexagonal maps, `bevy_ecs_tilemap` assumes regular hexagons and spaces two consecutive
+/// staggered rows (or columns) by 3/4 of the grid size. Tiled spaces them by half the tile size
+/// plus half the hexagon side length, so we adjust the grid size accordingly.
+/// For regular hexagons, both are equivalent and the grid size is the tile size.
+/// Isometric "staggered" maps are handled the same way, using a zero hexagon side length.
 pub fn get_grid_size(map: &Map) -> TilemapGridSize {
-    TilemapGridSize {
-        x: map.tile_width as f32,
-        y: map.tile_height as f32,
+    let (x, y) = (map.tile_width as f32, map.tile_height as f32);
+    if !matches!(
+        map.orientation,
+        tiled::Orientation::Hexagonal | tiled::Orientation::Staggered
+    ) {
+        return TilemapGridSize { x, y };
+    }
+    let hex_side_length = map.hex_side_length as f32;
+    match map.stagger_axis {
+        tiled::StaggerAxis::X => TilemapGridSize {
+            x: (x + hex_side_length) * 2. / 3.,
+            y,
+        },
+        tiled::StaggerAxis::Y => TilemapGridSize {
+            x,
+            y: (y + hex_side_length) * 2. / 3.,
+        },
+    }
+}
+
+/// Get all the layers of a [Map], including the ones nested in group layers.
+///
+/// Layers are returned in the order they appear in the `.tmx` file, a group layer coming
+/// right before its children. Each layer comes along with the index of its parent group layer,
+/// if any. The index of a layer in this list is the layer ID used by [TiledLayerCreated](crate::prelude::TiledLayerCreated)
+/// and can be used to retrieve a layer using [get_layer_from_index].
+pub fn get_all_layers(map: &Map) -> Vec<(Option<usize>, Layer<'_>)> {
+    fn push_layer<'a>(
+        layers: &mut Vec<(Option<usize>, Layer<'a>)>,
+        parent: Option<usize>,
+        layer: Layer<'a>,
+    ) {
+        let index = layers.len();
+        let layer_type = layer.layer_type();
+        layers.push((parent, layer));
+        if let LayerType::Group(group) = layer_type {
+            for child in group.layers() {
+                push_layer(layers, Some(index), child);
+            }
+        }
+    }
+
+    let mut layers = Vec::new();
+    for layer in map.layers() {
+        push_layer(&mut layers, None, layer);
     }
+    layers
+}
+
+/// Get the index of the map tileset referenced by a tile object.
+///
+/// Objects using a template reference the template tileset: the map tileset loaded from the
+/// same file is used instead. Returns [None] if the map does not use this tileset.
+pub(crate) fn get_tileset_index(map: &Map, location: &TilesetLocation) -> Option<usize> {
+    match location {
+        TilesetLocation::Map(index) => Some(*index),
+        TilesetLocation::Template(tileset) => map
+            .tilesets()
+            .iter()
+            .position(|t| Arc::ptr_eq(t, tileset) || t.source == tileset.source),
+    }
+}
+
+/// Get a [Layer] from its index in [get_all_layers] output.
+///
+/// For maps without group layers, this is equivalent to [Map::get_layer].
+/// Layers are browsed in the same order as [get_all_layers], without allocating.
+pub fn get_layer_from_index(map: &Map, index: usize) -> Option<Layer<'_>> {
+    // Look for the layer at `index` in the hierarchy starting with `layer`.
+    // If not found, returns the number of layers in this hierarchy.
+    fn find_layer(layer: Layer<'_>, index: usize) -> Result<Layer<'_>, usize> {
+        if index == 0 {
+            return Ok(layer);
+        }
+        let mut count = 1;
+        if let LayerType::Group(group) = layer.layer_type() {
+            for child in group.layers() {
+                match find_layer(child, index - count) {
+                    Ok(layer) => return Ok(layer),
+                    Err(n) => count += n,
+                }
+            }
+        }
+        Err(count)
+    }
+
+    let mut count = 0;
+    for layer in map.layers() {
+        match find_layer(layer, index - count) {
+            Ok(layer) => return Some(layer),
+            Err(n) => count += n,
+        }
+    }
+    None
+}
+
+/// Convert a [tiled::Color] to a Bevy [Color]
+pub fn from_tiled_color_to_bevy(color: &tiled::Color) -> Color {
+    Color::srgba_u8(color.red, color.green, color.blue, color.alpha)
 }
 
 /// Convert a position from Tiled space to world space.
 pub fn from_tiled_position_to_world_space(tiled_map: &TiledMap, tiled_position: Vec2) -> Vec2 {
     let map_size = tiled_map.tilemap_size;
     let map_height = tiled_map.rect.height();
-    let grid_size = get_grid_size(&tiled_map.map);
-    match get_map_type(&tiled_map.map) {
-        TilemapType::Square => {
+    let map = &tiled_map.map;
+    let grid_size = get_grid_size(map);
+    match (&map.orientation, &map.stagger_axis, &map.stagger_index) {
+        (tiled::Orientation::Orthogonal, _, _) => {
             tiled_map.tiled_offset
                 + Vec2 {
                     x: tiled_position.x,
                     y: map_height - tiled_position.y,
                 }
         }
-        TilemapType::Hexagon(HexCoordSystem::ColumnOdd) => {
+        (
+            tiled::Orientation::Hexagonal | tiled::Orientation::Staggered,
+            tiled::StaggerAxis::X,
+            tiled::StaggerIndex::Even,
+        ) => {
             tiled_map.tiled_offset
                 + Vec2 {
                     x: tiled_position.x,
                     y: map_height + grid_size.y / 2. - tiled_position.y,
                 }
         }
-        TilemapType::Hexagon(HexCoordSystem::ColumnEven) => {
+        (
+            tiled::Orientation::Hexagonal | tiled::Orientation::Staggered,
+            tiled::StaggerAxis::X,
+            tiled::StaggerIndex::Odd,
+        ) => {
             tiled_map.tiled_offset
                 + Vec2 {
                     x: tiled_position.x,
                     y: map_height - tiled_position.y,
                 }
         }
-        TilemapType::Hexagon(HexCoordSystem::RowOdd) => {
+        (
+            tiled::Orientation::Hexagonal | tiled::Orientation::Staggered,
+            tiled::StaggerAxis::Y,
+            tiled::StaggerIndex::Even,
+        ) => {
             tiled_map.tiled_offset
                 + Vec2 {
                     x: tiled_position.x,
                     y: map_height + grid_size.y / 4. - tiled_position.y,
                 }
         }
-        TilemapType::Hexagon(HexCoordSystem::RowEven) => {
+        (
+            tiled::Orientation::Hexagonal | tiled::Orientation::Staggered,
+            tiled::StaggerAxis::Y,
+            tiled::StaggerIndex::Odd,
+        ) => {
             tiled_map.tiled_offset
                 + Vec2 {
                     x: tiled_position.x - grid_size.x / 2.,
                     y: map_height + grid_size.y / 4. - tiled_position.y,
                 }
         }
-        TilemapType::Isometric(IsoCoordSystem::Diamond) => {
+        (tiled::Orientation::Isometric, _, _) => {
             let position = iso_projection(
                 tiled_position + tiled_map.tiled_offset,
                 &map_size,
@@ -91,25 +215,102 @@ pub fn from_tiled_position_to_world_space(tiled_map: &TiledMap, tiled_position:
                 y: map_height / 2. - grid_size.y / 2. - position.y,
             }
         }
-        TilemapType::Isometric(IsoCoordSystem::Staggered) => {
-            panic!("Isometric (Staggered) map is not supported");
-        }
-        _ => unreachable!(),
     }
 }
 
+/// Convert a world position to a [TilePos] of the given tilemap.
+///
+/// `tilemap_transform`, `grid_size`, `map_type` and `map_size` are the components of a
+/// [TiledMapTileLayerForTileset](crate::prelude::TiledMapTileLayerForTileset) [Entity]:
+/// the map [TiledMapAnchor](crate::prelude::TiledMapAnchor), layers offset, tileset offset,
+/// [TiledMapTileSizeOverride](crate::prelude::TiledMapTileSizeOverride) and
+/// [TiledMapYFlip](crate::prelude::TiledMapYFlip) are all taken into account through the tilemap [GlobalTransform].
+/// Returns [None] if the position is outside of the map bounds.
+pub fn world_to_tile_pos(
+    world_pos: Vec2,
+    tilemap_transform: &GlobalTransform,
+    grid_size: &TilemapGridSize,
+    map_type: &TilemapType,
+    map_size: &TilemapSize,
+) -> Option<TilePos> {
+    let local = tilemap_transform
+        .affine()
+        .inverse()
+        .transform_point3(world_pos.extend(0.))
+        .truncate();
+    TilePos::from_world_pos(&local, map_size, grid_size, map_type)
+}
+
+/// Convert a [TilePos] of the given tilemap to a world position, ie. the center of the tile.
+///
+/// This is the inverse of [world_to_tile_pos], see its documentation for arguments.
+pub fn tile_pos_to_world(
+    tile_pos: &TilePos,
+    tilemap_transform: &GlobalTransform,
+    grid_size: &TilemapGridSize,
+    map_type: &TilemapType,
+) -> Vec2 {
+    tilemap_transform
+        .transform_point(tile_pos.center_in_world(grid_size, map_type).extend(0.))
+        .truncate()
+}
+
+/// Compute the world-space bounding box of a tile of the given tilemap.
+///
+/// The bounding box is the one of the whole tile grid cell, see [world_to_tile_pos] for arguments.
+pub fn tile_aabb(
+    tile_pos: &TilePos,
+    tilemap_transform: &GlobalTransform,
+    grid_size: &TilemapGridSize,
+    map_type: &TilemapType,
+) -> Aabb2d {
+    let center = tile_pos.center_in_world(grid_size, map_type);
+    let half_size = Vec2::new(grid_size.x, grid_size.y) / 2.;
+    let (min, max) = [
+        Vec2::new(-half_size.x, -half_size.y),
+        Vec2::new(half_size.x, -half_size.y),
+        Vec2::new(-half_size.x, half_size.y),
+        Vec2::new(half_size.x, half_size.y),
+    ]
+    .into_iter()
+    .map(|corner| {
+        tilemap_transform
+            .transform_point((center + corner).extend(0.))
+            .truncate()
+    })
+    .fold((Vec2::MAX, Vec2::MIN), |(min, max), p| {
+        (min.min(p), max.max(p))
+    });
+    Aabb2d { min, max }
+}
+
 /// Iterate over all tiles from the given [TileLayer]
-pub fn for_each_tile<'a, F>(tiled_map: &'a TiledMap, tiles_layer: &TileLayer<'a>, mut f: F)
+pub fn for_each_tile<'a, F>(tiled_map: &'a TiledMap, tiles_layer: &TileLayer<'a>, f: F)
 where
     F: FnMut(LayerTile<'a>, &LayerTileData, TilePos, IVec2),
+{
+    for_each_tile_in_rows(tiled_map, tiles_layer, 0..tiled_map.tilemap_size.y, f);
+}
+
+/// Iterate over tiles from the given [TileLayer] which belong to the given rows.
+///
+/// Rows are counted from the top of the map, as in Tiled (ie. using the `index.y` passed to `f`).
+pub(crate) fn for_each_tile_in_rows<'a, F>(
+    tiled_map: &'a TiledMap,
+    tiles_layer: &TileLayer<'a>,
+    rows: Range<u32>,
+    mut f: F,
+) where
+    F: FnMut(LayerTile<'a>, &LayerTileData, TilePos, IVec2),
 {
     let tilemap_size = tiled_map.tilemap_size;
+    let rows = rows.start..rows.end.min(tilemap_size.y);
     match tiles_layer {
         TileLayer::Finite(layer) => {
             for x in 0..tilemap_size.x {
-                for y in 0..tilemap_size.y {
+                for mapped_y in rows.clone().rev() {
                     // Transform TMX coords into bevy coords.
-                    let mapped_y = tilemap_size.y - 1 - y;
+                    let y = tilemap_size.y - 1 - mapped_y;
                     let mapped_x = x as i32;
                     let mapped_y = mapped_y as i32;
 
@@ -138,8 +339,23 @@ where
                     chunk_pos.1 - tiled_map.topleft_chunk.1,
                 );
 
+                // Skip chunks which do not contain any of the requested rows
+                let chunk_rows = chunk_pos_mapped.1 * ChunkData::HEIGHT as i32
+                    ..(chunk_pos_mapped.1 + 1) * ChunkData::HEIGHT as i32;
+                if chunk_rows.end <= rows.start as i32 || chunk_rows.start >= rows.end as i32 {
+                    continue;
+                }
+
                 for x in 0..ChunkData::WIDTH {
                     for y in 0..ChunkData::HEIGHT {
+                        let index = IVec2 {
+                            x: chunk_pos_mapped.0 * ChunkData::WIDTH as i32 + x as i32,
+                            y: chunk_pos_mapped.1 * ChunkData::HEIGHT as i32 + y as i32,
+                        };
+                        if !rows.contains(&(index.y as u32)) {
+                            continue;
+                        }
+
                         // Invert y to match bevy coordinates.
                         let Some(layer_tile) = chunk.get_tile(x as i32, y as i32) else {
                             continue;
@@ -148,11 +364,6 @@ where
                             continue;
                         };
 
-                        let index = IVec2 {
-                            x: chunk_pos_mapped.0 * ChunkData::WIDTH as i32 + x as i32,
-                            y: chunk_pos_mapped.1 * ChunkData::HEIGHT as i32 + y as i32,
-                        };
-
                         f(
                             layer_tile,
                             layer_tile_data,
@@ -184,3 +395,102 @@ pub(crate) fn iso_projection(
         y: (fract.x + fract.y) * grid_size.y / 2.,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{load_map, map_asset};
+
+    fn assert_approx_eq(actual: f32, expected: f32) {
+        const EPSILON: f32 = 0.001;
+        assert!(
+            (actual - expected).abs() < EPSILON,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn hex_pointy_top_rows_share_edges() {
+        let (app, handle) = load_map("maps/hexagonal/finite_pointy_top_odd.tmx");
+        let map = map_asset(&app, &handle).map.as_ref();
+        let (grid_size, map_type) = (get_grid_size(map), get_map_type(map));
+        let origin = TilePos::new(0, 0).center_in_world(&grid_size, &map_type);
+        let right = TilePos::new(1, 0).center_in_world(&grid_size, &map_type);
+        let above = TilePos::new(0, 1).center_in_world(&grid_size, &map_type);
+        assert_approx_eq(right.x - origin.x, map.tile_width as f32);
+        assert_approx_eq(
+            above.y - origin.y,
+            (map.tile_height as f32 + map.hex_side_length as f32) / 2.,
+        );
+    }
+
+    #[test]
+    fn hex_flat_top_columns_share_edges() {
+        let (app, handle) = load_map("maps/hexagonal/finite_flat_top_even.tmx");
+        let map = map_asset(&app, &handle).map.as_ref();
+        let (grid_size, map_type) = (get_grid_size(map), get_map_type(map));
+        let origin = TilePos::new(0, 0).center_in_world(&grid_size, &map_type);
+        let right = TilePos::new(1, 0).center_in_world(&grid_size, &map_type);
+        let above = TilePos::new(0, 1).center_in_world(&grid_size, &map_type);
+        assert_approx_eq(
+            right.x - origin.x,
+            (map.tile_width as f32 + map.hex_side_length as f32) / 2.,
+        );
+        assert_approx_eq(above.y - origin.y, map.tile_height as f32);
+    }
+
+    #[test]
+    fn world_to_tile_pos_accounts_for_tilemap_transform() {
+        let (app, handle) = load_map("maps/hexagonal/finite_pointy_top_odd.tmx");
+        let map = map_asset(&app, &handle).map.as_ref();
+        let (grid_size, map_type) = (get_grid_size(map), get_map_type(map));
+        let map_size = TilemapSize::new(map.width, map.height);
+        // Mirrored and scaled, as a tilemap using TiledMapYFlip(false) and TiledMapTileSizeOverride
+        let tilemap_transform = GlobalTransform::from(
+            Transform::from_xyz(-100., 50., 0.).with_scale(Vec3::new(2., -1.5, 1.)),
+        );
+        for tile_pos in [TilePos::new(0, 0), TilePos::new(3, 2)] {
+            let world_pos = tile_pos_to_world(&tile_pos, &tilemap_transform, &grid_size, &map_type);
+            assert_eq!(
+                world_to_tile_pos(
+                    world_pos,
+                    &tilemap_transform,
+                    &grid_size,
+                    &map_type,
+                    &map_size
+                ),
+                Some(tile_pos)
+            );
+            let aabb = tile_aabb(&tile_pos, &tilemap_transform, &grid_size, &map_type);
+            assert!((aabb.center() - world_pos).length() < 0.001);
+            assert!(
+                (aabb.half_size() - Vec2::new(grid_size.x, grid_size.y * 0.75)).length() < 0.001
+            );
+        }
+        assert_eq!(
+            world_to_tile_pos(
+                Vec2::new(-200., 0.),
+                &tilemap_transform,
+                &grid_size,
+                &map_type,
+                &map_size
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn layer_from_index_matches_all_layers() {
+        let (app, handle) = load_map("maps/orthogonal/group_layers.tmx");
+        let map = map_asset(&app, &handle).map.as_ref();
+        let layers = get_all_layers(map);
+        assert!(layers.iter().any(|(parent, _)| parent.is_some()));
+        for (index, (_, layer)) in layers.iter().enumerate() {
+            assert_eq!(
+                get_layer_from_index(map, index).map(|l| l.id()),
+                Some(layer.id())
+            );
+        }
+        assert!(get_layer_from_index(map, layers.len()).is_none());
+    }
+}
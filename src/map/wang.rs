@@ -0,0 +1,143 @@
+//! This module contains the [TiledWangSets] [Resource], exposing tilesets Wang sets.
+//!
+//! Wang sets describe terrain transitions: they can be used to pick the tile to use when
+//! painting terrains at runtime.
+
+use crate::prelude::*;
+use bevy::{prelude::*, utils::HashMap};
+use bevy_ecs_tilemap::prelude::*;
+
+/// Terrain transition rules of a Tiled Wang set.
+///
+/// Maps the [WangId](tiled::WangId) of each tile of the Wang set (ie. the Wang color of each
+/// of its edges and corners) to the [TileTextureIndex] of the tiles matching it.
+#[derive(Default, Clone, Debug)]
+pub struct TiledWangSet {
+    /// Names of the Wang colors of this Wang set.
+    ///
+    /// Wang color `0` means "no color": color at index `0` of this [Vec] is Wang color `1`.
+    pub colors: Vec<String>,
+    /// Tiles of this Wang set, keyed using their [WangId](tiled::WangId) inner array.
+    ///
+    /// Several tiles can share the same [WangId](tiled::WangId), for instance variations of
+    /// the same terrain: they are sorted by tile ID.
+    pub tiles: HashMap<[u8; 8], Vec<TileTextureIndex>>,
+}
+
+impl TiledWangSet {
+    /// Retrieve the [TileTextureIndex] of all the tiles matching given [WangId](tiled::WangId).
+    ///
+    /// Returns an empty slice if no tile matches.
+    pub fn get(&self, wang_id: &tiled::WangId) -> &[TileTextureIndex] {
+        self.tiles.get(&wang_id.0).map_or(&[], Vec::as_slice)
+    }
+}
+
+/// [Resource] indexing Wang sets of loaded maps using their map [AssetId], their tileset
+/// index and their name.
+///
+/// Kept up to date in [PostUpdate] as maps are loaded, reloaded or unloaded.
+/// It can be used by a system to replace a tile [TileTextureIndex] when the terrain of its
+/// neighbours changes, for instance for runtime terrain painting.
+#[derive(Resource, Default, Clone, Debug)]
+pub struct TiledWangSets(pub HashMap<(AssetId<TiledMap>, usize, String), TiledWangSet>);
+
+impl TiledWangSets {
+    /// Retrieve a [TiledWangSet] using its map [AssetId], its tileset index and its name.
+    pub fn get(
+        &self,
+        map_asset_id: AssetId<TiledMap>,
+        tileset_index: usize,
+        name: &str,
+    ) -> Option<&TiledWangSet> {
+        self.0.get(&(map_asset_id, tileset_index, name.to_string()))
+    }
+}
+
+pub(crate) fn update_wang_sets(
+    mut wang_sets: ResMut<TiledWangSets>,
+    maps: Res<Assets<TiledMap>>,
+    mut map_events: EventReader<AssetEvent<TiledMap>>,
+) {
+    for event in map_events.read() {
+        let (AssetEvent::LoadedWithDependencies { id }
+        | AssetEvent::Modified { id }
+        | AssetEvent::Removed { id }) = event
+        else {
+            continue;
+        };
+        wang_sets.0.retain(|(map_id, _, _), _| map_id != id);
+        let Some(tiled_map) = maps.get(*id) else {
+            continue;
+        };
+        for (tileset_index, tileset) in tiled_map.map.tilesets().iter().enumerate() {
+            let Some(t) = tiled_map.tilesets.get(&tileset_index) else {
+                continue;
+            };
+            for wang_set in &tileset.wang_sets {
+                let mut wang_tiles = wang_set.wang_tiles.iter().collect::<Vec<_>>();
+                wang_tiles.sort_by_key(|(tile_id, _)| **tile_id);
+                let mut tiles: HashMap<[u8; 8], Vec<TileTextureIndex>> = HashMap::new();
+                for (tile_id, wang_tile) in wang_tiles {
+                    if let Some(index) = t.texture_index(*tile_id) {
+                        tiles
+                            .entry(wang_tile.wang_id.0)
+                            .or_default()
+                            .push(TileTextureIndex(index));
+                    }
+                }
+                wang_sets.0.insert(
+                    (*id, tileset_index, wang_set.name.clone()),
+                    TiledWangSet {
+                        colors: wang_set
+                            .wang_colors
+                            .iter()
+                            .map(|color| color.name.clone())
+                            .collect(),
+                        tiles,
+                    },
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::load_map;
+
+    #[test]
+    fn wang_tiles_sharing_a_wang_id() {
+        let (mut app, handle) = load_map("maps/demo.tmx");
+        app.update();
+        let tileset_index = app
+            .world()
+            .resource::<Assets<TiledMap>>()
+            .get(&handle)
+            .unwrap()
+            .map
+            .tilesets()
+            .iter()
+            .position(|tileset| tileset.name == "Tileset floor - 16x16")
+            .unwrap();
+        let wang_set = app
+            .world()
+            .resource::<TiledWangSets>()
+            .get(handle.id(), tileset_index, "Grass / Sand")
+            .expect("Wang set should be indexed once the map is loaded");
+
+        assert_eq!(wang_set.colors, vec!["Sand", "Grass"]);
+        // Tileset uses a single image: texture index is the tile ID
+        assert_eq!(
+            wang_set.get(&tiled::WangId([2, 2, 1, 1, 1, 2, 2, 2])),
+            &[TileTextureIndex(0)]
+        );
+        // Grass variations all use the same Wang ID
+        let grass = wang_set.get(&tiled::WangId([2; 8]));
+        assert!(grass.len() >= 10);
+        assert!(grass.is_sorted_by_key(|index| index.0));
+        assert!((55..=64).all(|id| grass.contains(&TileTextureIndex(id))));
+        assert!(wang_set.get(&tiled::WangId([0; 8])).is_empty());
+    }
+}
@@ -74,11 +74,28 @@ impl From<&TiledName> for TiledNameFilter {
 
 impl TiledNameFilter {
     /// Determine if provided [str] matches the filter.
+    ///
+    /// As for [TiledName::Names], comparison is case-insensitive and leading/trailing whitespace
+    /// of provided name is trimmed.
     pub fn contains(&self, name: &str) -> bool {
         match self {
             TiledNameFilter::All => true,
-            TiledNameFilter::Names(names) => names.contains(name),
+            TiledNameFilter::Names(names) => names.contains(&name.trim().to_lowercase()),
             TiledNameFilter::None => false,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_filter_is_case_insensitive() {
+        let filter = TiledNameFilter::from(&TiledName::Names(vec![String::from(" Collision ")]));
+        assert!(filter.contains("Collision"));
+        assert!(filter.contains("collision"));
+        assert!(filter.contains("  COLLISION"));
+        assert!(!filter.contains("Collisions"));
+    }
+}
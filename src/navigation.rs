@@ -0,0 +1,414 @@
+//! This module contains a navigation grid builder, reading walkability data from Tiled layers.
+//!
+//! You need to enable the `navigation` feature to use it.
+//!
+//! Once a map is spawned, a [TiledNavGrid] [Component] is inserted on the map [Entity]
+//! and a [TiledNavGridCreated] event is sent.
+//! It can then be consumed by any pathfinding algorithm or crate.
+
+use crate::prelude::*;
+use bevy::prelude::*;
+use bevy_ecs_tilemap::prelude::*;
+
+/// `bevy_ecs_tiled` navigation exports.
+pub mod prelude {
+    pub use super::{
+        TiledNavGrid, TiledNavGridCreated, TiledNavigationConfig, TiledNavigationPlugin,
+    };
+}
+
+/// Configuration for the [TiledNavigationPlugin]
+#[derive(Resource, Reflect, Clone, Debug)]
+#[reflect(Resource, Debug)]
+pub struct TiledNavigationConfig {
+    /// Names of the layers containing walkability data.
+    ///
+    /// For tiles layers, every cell containing a tile is walkable.
+    /// For objects layers, every cell whose center is inside an object shape is walkable.
+    /// Group layers are browsed recursively.
+    pub walkable_layer_names: Vec<String>,
+    /// Name of a boolean tile property marking tiles as walkable.
+    ///
+    /// If set, every cell of any tiles layer containing a tile with this property
+    /// set to `true` in its tileset is walkable.
+    pub walkable_tile_property: Option<String>,
+}
+
+impl Default for TiledNavigationConfig {
+    fn default() -> Self {
+        Self {
+            walkable_layer_names: vec![String::from("Walkable"), String::from("Navigation")],
+            walkable_tile_property: None,
+        }
+    }
+}
+
+/// `bevy_ecs_tiled` navigation [Plugin]
+///
+/// Build a [TiledNavGrid] for each spawned map and send a [TiledNavGridCreated] event:
+///
+/// ```rust,no_run
+/// use bevy::prelude::*;
+/// use bevy_ecs_tiled::prelude::*;
+///
+/// App::new()
+///     .add_plugins(TiledNavigationPlugin::default());
+/// ```
+#[derive(Default, Clone, Debug)]
+pub struct TiledNavigationPlugin(pub TiledNavigationConfig);
+
+impl Plugin for TiledNavigationPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<TiledNavigationConfig>()
+            .register_type::<TiledNavGrid>()
+            .add_event::<TiledNavGridCreated>()
+            .register_type::<TiledNavGridCreated>()
+            .insert_resource(self.0.clone())
+            .add_systems(PostUpdate, build_nav_grid);
+    }
+}
+
+/// Walkability grid of a Tiled map.
+///
+/// This [Component] is inserted on the map [Entity] by the [TiledNavigationPlugin].
+/// Grid has the same size and uses the same coordinates as the map [TilePos], including
+/// when using [TiledMapYFlip].
+#[derive(Component, Default, Reflect, Clone, Debug)]
+#[reflect(Component, Default, Debug)]
+pub struct TiledNavGrid {
+    /// Grid width, in tiles
+    pub width: u32,
+    /// Grid height, in tiles
+    pub height: u32,
+    /// Walkability of each cell, row by row starting from [TilePos] Y = 0: `true` means walkable
+    pub cells: Vec<bool>,
+}
+
+/// Event sent once the [TiledNavGrid] of a map has been built.
+#[derive(Event, Reflect, Clone, Debug)]
+#[reflect(Debug)]
+pub struct TiledNavGridCreated {
+    /// Map [Entity] the [TiledNavGrid] was inserted on
+    pub entity: Entity,
+    /// Position of all the walkable cells
+    pub walkable_tiles: Vec<TilePos>,
+}
+
+impl TiledNavGrid {
+    /// Create a new grid where no cell is walkable.
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![false; (width * height) as usize],
+        }
+    }
+
+    /// Returns `true` if the cell at given position is walkable.
+    ///
+    /// Out of bounds positions are never walkable.
+    pub fn is_walkable(&self, pos: &TilePos) -> bool {
+        self.index(pos).is_some_and(|i| self.cells[i])
+    }
+
+    /// Set walkability of the cell at given position.
+    ///
+    /// Out of bounds positions are ignored.
+    pub fn set_walkable(&mut self, pos: &TilePos, walkable: bool) {
+        if let Some(i) = self.index(pos) {
+            self.cells[i] = walkable;
+        }
+    }
+
+    /// Iterate over the position of all walkable cells.
+    pub fn walkable_tiles(&self) -> impl Iterator<Item = TilePos> + '_ {
+        self.cells
+            .iter()
+            .enumerate()
+            .filter(|(_, walkable)| **walkable)
+            .map(|(i, _)| TilePos::new(i as u32 % self.width, i as u32 / self.width))
+    }
+
+    /// Mirror the grid along the Y axis, ie. to use Tiled Y coordinates.
+    fn flip_rows(&mut self) {
+        let width = self.width as usize;
+        let rows = self.cells.chunks(width.max(1)).rev();
+        self.cells = rows.flatten().copied().collect();
+    }
+
+    fn index(&self, pos: &TilePos) -> Option<usize> {
+        (pos.x < self.width && pos.y < self.height).then_some((pos.y * self.width + pos.x) as usize)
+    }
+}
+
+fn build_nav_grid(
+    mut commands: Commands,
+    mut map_events: EventReader<TiledMapCreated>,
+    mut grid_events: EventWriter<TiledNavGridCreated>,
+    map_asset: Res<Assets<TiledMap>>,
+    map_query: Query<(Option<&TiledMapYFlip>, Option<&TiledMapTypeOverride>)>,
+    config: Res<TiledNavigationConfig>,
+) {
+    for e in map_events.read() {
+        let Some(tiled_map) = e.get_map_asset(&map_asset) else {
+            continue;
+        };
+        let Ok((y_flip, map_type_override)) = map_query.get(e.entity) else {
+            continue;
+        };
+        let map_type = map_type_override.map_or_else(|| get_map_type(&tiled_map.map), |o| o.0);
+        let mut grid = build_grid(tiled_map, &config);
+        if !y_flip.copied().unwrap_or_default().is_inverted(&map_type) {
+            grid.flip_rows();
+        }
+        debug!(
+            "Built navigation grid for map {:?} ({}x{})",
+            e.entity, grid.width, grid.height
+        );
+        grid_events.send(TiledNavGridCreated {
+            entity: e.entity,
+            walkable_tiles: grid.walkable_tiles().collect(),
+        });
+        commands.entity(e.entity).insert(grid);
+    }
+}
+
+/// Build the [TiledNavGrid] of given map from the layers designated in [TiledNavigationConfig].
+///
+/// Grid uses inverted Y coordinates, ie. the default [TiledMapYFlip].
+fn build_grid(tiled_map: &TiledMap, config: &TiledNavigationConfig) -> TiledNavGrid {
+    let size = tiled_map.tilemap_size;
+    let mut grid = TiledNavGrid::new(size.x, size.y);
+
+    let mut to_process = Vec::from_iter(tiled_map.map.layers());
+    while let Some(layer) = to_process.pop() {
+        if let tiled::LayerType::Group(group) = layer.layer_type() {
+            to_process.extend(group.layers());
+            continue;
+        }
+        let is_walkable_layer = config.walkable_layer_names.contains(&layer.name);
+        match layer.layer_type() {
+            tiled::LayerType::Tiles(tiles_layer) => {
+                if !is_walkable_layer && config.walkable_tile_property.is_none() {
+                    continue;
+                }
+                for_each_tile(tiled_map, &tiles_layer, |layer_tile, _, tile_pos, _| {
+                    if is_walkable_layer || is_walkable_tile(&layer_tile, config) {
+                        grid.set_walkable(&tile_pos, true);
+                    }
+                });
+            }
+            tiled::LayerType::Objects(objects_layer) if is_walkable_layer => {
+                for object in objects_layer.object_data() {
+                    project_object(tiled_map, object, &mut grid);
+                }
+            }
+            _ => {}
+        }
+    }
+    grid
+}
+
+/// Returns `true` if given tile has the walkable property from [TiledNavigationConfig] set to `true`.
+fn is_walkable_tile(layer_tile: &tiled::LayerTile, config: &TiledNavigationConfig) -> bool {
+    let Some(property) = &config.walkable_tile_property else {
+        return false;
+    };
+    layer_tile.get_tile().is_some_and(|tile| {
+        matches!(
+            tile.properties.get(property),
+            Some(tiled::PropertyValue::BoolValue(true))
+        )
+    })
+}
+
+/// Mark as walkable all cells whose center is inside given object shape.
+fn project_object(tiled_map: &TiledMap, object: &tiled::ObjectData, grid: &mut TiledNavGrid) {
+    let points = match &object.shape {
+        tiled::ObjectShape::Rect { width, height } => {
+            vec![(0., 0.), (*width, 0.), (*width, *height), (0., *height)]
+        }
+        tiled::ObjectShape::Ellipse { width, height } => (0..16)
+            .map(|i| {
+                let angle = i as f32 * std::f32::consts::TAU / 16.;
+                (
+                    width / 2. * (1. + angle.cos()),
+                    height / 2. * (1. + angle.sin()),
+                )
+            })
+            .collect(),
+        tiled::ObjectShape::Polygon { points } => points.clone(),
+        _ => return,
+    };
+
+    // Object rotation is clockwise and in degrees, around object origin
+    let rotation = Vec2::from_angle(object.rotation.to_radians());
+    let polygon: Vec<Vec2> = points
+        .into_iter()
+        .map(|(x, y)| {
+            let tiled_position = Vec2::new(object.x, object.y) + rotation.rotate(Vec2::new(x, y));
+            from_tiled_position_to_world_space(tiled_map, tiled_position)
+        })
+        .collect();
+
+    let map_type = get_map_type(&tiled_map.map);
+    let grid_size = get_grid_size(&tiled_map.map);
+    for x in 0..grid.width {
+        for y in 0..grid.height {
+            let tile_pos = TilePos::new(x, y);
+            // Tilemaps are offset by half a tile from the layer origin (see loader::load_tiles_layer())
+            let center = tile_pos.center_in_world(&grid_size, &map_type)
+                + Vec2::new(grid_size.x / 2., grid_size.y / 2.);
+            if is_inside_polygon(center, &polygon) {
+                grid.set_walkable(&tile_pos, true);
+            }
+        }
+    }
+}
+
+/// Even-odd rule point in polygon test.
+fn is_inside_polygon(point: Vec2, polygon: &[Vec2]) -> bool {
+    let mut inside = false;
+    let mut j = polygon.len().wrapping_sub(1);
+    for i in 0..polygon.len() {
+        let (a, b) = (polygon[i], polygon[j]);
+        if (a.y > point.y) != (b.y > point.y)
+            && point.x < (b.x - a.x) * (point.y - a.y) / (b.y - a.y) + a.x
+        {
+            inside = !inside;
+        }
+        j = i;
+    }
+    inside
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{load_map, spawn_map_in, test_app};
+
+    fn config(walkable_layer_names: &[&str]) -> TiledNavigationConfig {
+        TiledNavigationConfig {
+            walkable_layer_names: walkable_layer_names.iter().map(|s| s.to_string()).collect(),
+            walkable_tile_property: None,
+        }
+    }
+
+    #[test]
+    fn grid_from_tiles_layer() {
+        let (app, handle) = load_map("maps/orthogonal/finite.tmx");
+        let tiled_map = app
+            .world()
+            .resource::<Assets<TiledMap>>()
+            .get(&handle)
+            .unwrap();
+        let layer = get_layer_from_index(&tiled_map.map, 1).unwrap();
+        assert_eq!(layer.name, "Tile Layer 1");
+        let tiles_layer = layer.as_tile_layer().unwrap();
+
+        let grid = build_grid(tiled_map, &config(&["Tile Layer 1"]));
+        assert_eq!((grid.width, grid.height), (10, 10));
+        let mut expected = TiledNavGrid::new(10, 10);
+        for_each_tile(tiled_map, &tiles_layer, |_, _, tile_pos, _| {
+            expected.set_walkable(&tile_pos, true);
+        });
+        assert_eq!(grid.cells, expected.cells);
+        // Map is surrounded by a wall of tiles, with a mostly empty inside
+        assert!(grid.is_walkable(&TilePos::new(0, 0)));
+        assert!(!grid.is_walkable(&TilePos::new(1, 8)));
+    }
+
+    #[test]
+    fn grid_from_objects_layer() {
+        let (app, handle) = load_map("maps/orthogonal/finite.tmx");
+        let tiled_map = app
+            .world()
+            .resource::<Assets<TiledMap>>()
+            .get(&handle)
+            .unwrap();
+
+        let grid = build_grid(tiled_map, &config(&["Collisions"]));
+        // First and second objects are rectangles covering the top and bottom rows of the map
+        for x in 0..10 {
+            assert!(grid.is_walkable(&TilePos::new(x, 9)));
+            assert!(grid.is_walkable(&TilePos::new(x, 0)));
+        }
+        // Inside the ellipse at the top left of the map
+        assert!(grid.is_walkable(&TilePos::new(1, 7)));
+        assert!(!grid.is_walkable(&TilePos::new(0, 5)));
+        assert!(!grid.is_walkable(&TilePos::new(10, 0)));
+    }
+
+    #[test]
+    fn grid_rows_flip() {
+        let mut grid = TiledNavGrid::new(2, 3);
+        grid.set_walkable(&TilePos::new(1, 0), true);
+        grid.flip_rows();
+        assert!(grid.is_walkable(&TilePos::new(1, 2)));
+        assert_eq!(grid.walkable_tiles().count(), 1);
+    }
+
+    #[test]
+    fn grid_without_walkable_layers() {
+        let (app, handle) = load_map("maps/orthogonal/finite.tmx");
+        let tiled_map = app
+            .world()
+            .resource::<Assets<TiledMap>>()
+            .get(&handle)
+            .unwrap();
+
+        // Layers which are not listed in the configuration are blocked
+        let grid = build_grid(tiled_map, &TiledNavigationConfig::default());
+        assert_eq!(grid.cells.len(), 100);
+        assert_eq!(grid.walkable_tiles().count(), 0);
+    }
+
+    #[test]
+    fn grid_from_walkable_tile_property() {
+        let (app, handle) = load_map("maps/orthogonal/finite.tmx");
+        let tiled_map = app
+            .world()
+            .resource::<Assets<TiledMap>>()
+            .get(&handle)
+            .unwrap();
+
+        // Only the tile used for the map border has this property set to `true`
+        let config = TiledNavigationConfig {
+            walkable_layer_names: Vec::new(),
+            walkable_tile_property: Some(String::from("RANDOM PROPERTY THAT WILL NOT BE LOADED")),
+        };
+        let grid = build_grid(tiled_map, &config);
+        assert_eq!(grid.walkable_tiles().count(), 36);
+        assert!(grid
+            .walkable_tiles()
+            .all(|pos| pos.x == 0 || pos.x == 9 || pos.y == 0 || pos.y == 9));
+
+        // Unknown properties are never walkable
+        let config = TiledNavigationConfig {
+            walkable_tile_property: Some(String::from("Unknown")),
+            ..config
+        };
+        assert_eq!(build_grid(tiled_map, &config).walkable_tiles().count(), 0);
+    }
+
+    #[test]
+    fn grid_created_event() {
+        let mut app = test_app();
+        app.add_plugins(TiledNavigationPlugin(config(&["Tile Layer 1"])));
+        let map_entity = spawn_map_in(&mut app, "maps/orthogonal/finite.tmx", ());
+
+        let grid = app
+            .world()
+            .get::<TiledNavGrid>(map_entity)
+            .expect("grid should be inserted on the map");
+        let events = app.world().resource::<Events<TiledNavGridCreated>>();
+        let events = events.get_cursor().read(events).collect::<Vec<_>>();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].entity, map_entity);
+        assert_eq!(
+            events[0].walkable_tiles,
+            grid.walkable_tiles().collect::<Vec<_>>()
+        );
+        assert!(!events[0].walkable_tiles.is_empty());
+    }
+}
@@ -86,10 +86,18 @@ impl TiledPhysicsBackend for TiledPhysicsAvianBackend {
                 }
                 .unwrap_or_default()
             }
-            TiledCollider::TilesLayer { layer_id: _ } => {
-                let mut composables = vec![];
+            TiledCollider::TilesLayer { .. } => {
+                let (rects, tiles) = collider.get_merged_tiles(tiled_map, filter);
+                let mut composables = Vec::from_iter(rects.into_iter().map(|rect| {
+                    let center = rect.center();
+                    let half_size = rect.half_size();
+                    (
+                        Isometry::<Real>::new(center.into(), 0.),
+                        SharedShape::cuboid(half_size.x, half_size.y),
+                    )
+                }));
                 let mut spawn_infos = vec![];
-                for (tile_position, tile) in collider.get_tiles(tiled_map) {
+                for (tile_position, tile) in tiles {
                     if let Some(collision) = &tile.collision {
                         compose_tiles(
                             commands,
@@ -102,6 +110,21 @@ impl TiledPhysicsBackend for TiledPhysicsAvianBackend {
                         );
                     }
                 }
+                if !collider.merge_tile_colliders() {
+                    for (iso, shape) in composables.drain(..) {
+                        let collider: Collider = shape.into();
+                        spawn_infos.push(TiledColliderSpawnInfos {
+                            name: "Avian[Tile]".to_string(),
+                            entity: commands.spawn(collider).id(),
+                            transform: Transform::from_xyz(
+                                iso.translation.vector.x,
+                                iso.translation.vector.y,
+                                0.,
+                            )
+                            .with_rotation(Quat::from_rotation_z(iso.rotation.angle())),
+                        });
+                    }
+                }
                 if !composables.is_empty() {
                     let collider: Collider = SharedShape::compound(composables).into();
                     spawn_infos.push(TiledColliderSpawnInfos {
@@ -114,6 +137,29 @@ impl TiledPhysicsBackend for TiledPhysicsAvianBackend {
             }
         }
     }
+
+    fn make_sensor(&self, commands: &mut Commands, collider_entity: Entity) {
+        commands.entity(collider_entity).insert(Sensor);
+    }
+
+    fn set_material(
+        &self,
+        commands: &mut Commands,
+        collider_entity: Entity,
+        friction: Option<f32>,
+        restitution: Option<f32>,
+    ) {
+        if let Some(friction) = friction {
+            commands
+                .entity(collider_entity)
+                .insert(Friction::new(friction));
+        }
+        if let Some(restitution) = restitution {
+            commands
+                .entity(collider_entity)
+                .insert(Restitution::new(restitution));
+        }
+    }
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -172,10 +218,14 @@ fn get_position_and_shape(shape: &ObjectShape) -> Option<(Vec2, SharedShape, boo
             Some((pos, shape, true))
         }
         ObjectShape::Ellipse { width, height } => {
-            let shape = SharedShape::new(EllipseColliderShape(Ellipse::new(
-                width / 2.0,
-                height / 2.0,
-            )));
+            let shape = if width == height {
+                SharedShape::ball(width / 2.)
+            } else {
+                SharedShape::new(EllipseColliderShape(Ellipse::new(
+                    width / 2.0,
+                    height / 2.0,
+                )))
+            };
             let pos = Vec2::new(width / 2., -height / 2.);
             Some((pos, shape, true))
         }
@@ -1,6 +1,10 @@
 //! Module that handles colliders
 use crate::prelude::*;
-use bevy::prelude::*;
+use bevy::{
+    prelude::*,
+    utils::{HashMap, HashSet},
+};
+use bevy_ecs_tilemap::map::TilemapType;
 use tiled::{Layer, Object, Tile};
 
 /// Marker component for colliders
@@ -10,12 +14,21 @@ use tiled::{Layer, Object, Tile};
 pub struct TiledColliderMarker;
 
 /// Describe the type of the [TiledCollider].
-#[derive(Copy, Clone, Debug)]
+#[derive(Clone, Debug)]
 pub enum TiledCollider {
     /// Collider is created by a tiles [Layer]
     TilesLayer {
         /// ID of the layer
         layer_id: usize,
+        /// Filters on layer name and object name selecting the polygon objects used as collision
+        /// shape for the tiles they are placed on, or [None] to keep tiles own collision shapes.
+        ///
+        /// See [TiledPhysicsSettings::use_polygon_objects_for_tile_shapes](super::TiledPhysicsSettings::use_polygon_objects_for_tile_shapes).
+        polygon_objects_filter: Option<(TiledName, TiledName)>,
+        /// Whether tiles colliders should be merged into a single collider.
+        ///
+        /// See [TiledPhysicsSettings::merge_tile_colliders](super::TiledPhysicsSettings::merge_tile_colliders).
+        merge_tile_colliders: bool,
     },
     /// Collider is created by an [Object]
     Object {
@@ -37,7 +50,58 @@ impl TiledCollider {
 
     /// Create a new [TiledCollider::TilesLayer].
     pub fn from_tiles_layer(layer_id: usize) -> Self {
-        Self::TilesLayer { layer_id }
+        Self::TilesLayer {
+            layer_id,
+            polygon_objects_filter: None,
+            merge_tile_colliders: true,
+        }
+    }
+
+    /// Use polygon objects as collision shape for the tiles they are placed on.
+    ///
+    /// Only polygon objects from a layer matching `objects_layer_filter` and whose name
+    /// matches `objects_filter` are used, ie. the ones which get their own collider.
+    /// Only relevant for [TiledCollider::TilesLayer].
+    pub fn with_polygon_objects_for_tile_shapes(
+        mut self,
+        objects_layer_filter: TiledName,
+        objects_filter: TiledName,
+    ) -> Self {
+        if let Self::TilesLayer {
+            polygon_objects_filter,
+            ..
+        } = &mut self
+        {
+            *polygon_objects_filter = Some((objects_layer_filter, objects_filter));
+        }
+        self
+    }
+
+    /// Set whether tiles colliders should be merged into a single collider.
+    ///
+    /// Only relevant for [TiledCollider::TilesLayer].
+    pub fn with_merge_tile_colliders(mut self, enabled: bool) -> Self {
+        if let Self::TilesLayer {
+            merge_tile_colliders,
+            ..
+        } = &mut self
+        {
+            *merge_tile_colliders = enabled;
+        }
+        self
+    }
+
+    /// Returns `true` if tiles colliders should be merged into a single collider.
+    ///
+    /// Always `true` for [TiledCollider::Object]: colliders of a tile object are always merged.
+    pub fn merge_tile_colliders(&self) -> bool {
+        match self {
+            Self::TilesLayer {
+                merge_tile_colliders,
+                ..
+            } => *merge_tile_colliders,
+            Self::Object { .. } => true,
+        }
     }
 }
 
@@ -48,8 +112,10 @@ impl<'a> TiledCollider {
             TiledCollider::Object {
                 layer_id,
                 object_id: _,
-            } => tiled_map.map.get_layer(*layer_id),
-            TiledCollider::TilesLayer { layer_id } => tiled_map.map.get_layer(*layer_id),
+            } => get_layer_from_index(&tiled_map.map, *layer_id),
+            TiledCollider::TilesLayer { layer_id, .. } => {
+                get_layer_from_index(&tiled_map.map, *layer_id)
+            }
         }
     }
 
@@ -59,9 +125,7 @@ impl<'a> TiledCollider {
             TiledCollider::Object {
                 layer_id,
                 object_id,
-            } => tiled_map
-                .map
-                .get_layer(*layer_id)
+            } => get_layer_from_index(&tiled_map.map, *layer_id)
                 .and_then(|layer| layer.as_object_layer())
                 .and_then(|object_layer| object_layer.get_object(*object_id)),
             _ => None,
@@ -69,29 +133,229 @@ impl<'a> TiledCollider {
     }
 
     /// Get a vector containing tiles in this layer as well as their relative position to their parent tileset layer.
+    ///
+    /// When `polygon_objects_filter` is set, tiles matched by a polygon object
+    /// are left out: their collider is the one spawned for the polygon object.
     pub fn get_tiles(&self, tiled_map: &'a TiledMap) -> Vec<(Vec2, Tile<'a>)> {
         match self {
-            TiledCollider::TilesLayer { layer_id } => tiled_map
-                .map
-                .get_layer(*layer_id)
+            TiledCollider::TilesLayer {
+                layer_id,
+                polygon_objects_filter,
+                ..
+            } => get_layer_from_index(&tiled_map.map, *layer_id)
                 .and_then(|layer| layer.as_tile_layer())
                 .map(|layer| {
                     let mut out = vec![];
                     for_each_tile(tiled_map, &layer, |layer_tile, _, tile_pos, _| {
                         if let Some(tile) = layer_tile.get_tile() {
-                            let tile_coords = tile_pos.center_in_world(
-                                &get_grid_size(&tiled_map.map),
-                                &get_map_type(&tiled_map.map),
-                            );
+                            let tileset = tile.tileset();
+                            let tile_coords =
+                                tile_pos.center_in_world(
+                                    &get_grid_size(&tiled_map.map),
+                                    &get_map_type(&tiled_map.map),
+                                ) + Vec2::new(tileset.offset_x as f32, -tileset.offset_y as f32);
                             out.push((tile_coords, tile));
                         }
                     });
+                    if let Some((layer_filter, object_filter)) = polygon_objects_filter {
+                        remove_tiles_matching_polygon_objects(
+                            tiled_map,
+                            &mut out,
+                            &TiledNameFilter::from(layer_filter),
+                            &TiledNameFilter::from(object_filter),
+                        );
+                    }
                     out
                 })
                 .unwrap_or_default(),
             _ => vec![],
         }
     }
+
+    /// Get tiles in this layer, merging tiles fully covered by a single rectangle collision shape.
+    ///
+    /// When [TiledCollider::merge_tile_colliders] is `true` and the map is orthogonal, adjacent
+    /// tiles whose only collision shape (matching `filter`) is an unrotated rectangle covering
+    /// the whole tile are greedily merged into larger rectangles, first row-by-row then
+    /// column-by-column.
+    /// Returns these rectangles, relative to their parent tiles layer, as well as remaining
+    /// tiles, in the same format as [TiledCollider::get_tiles].
+    pub fn get_merged_tiles(
+        &self,
+        tiled_map: &'a TiledMap,
+        filter: &TiledNameFilter,
+    ) -> (Vec<Rect>, Vec<(Vec2, Tile<'a>)>) {
+        let tiles = self.get_tiles(tiled_map);
+        if !self.merge_tile_colliders()
+            || !matches!(get_map_type(&tiled_map.map), TilemapType::Square)
+        {
+            return (vec![], tiles);
+        }
+
+        let grid_size = get_grid_size(&tiled_map.map);
+        let grid_size = Vec2::new(grid_size.x, grid_size.y);
+        let mut cells = HashSet::default();
+        let mut remaining = vec![];
+        for (tile_position, tile) in tiles {
+            let cell = (tile_position / grid_size).round();
+            if cell * grid_size == tile_position && is_full_tile_rect(&tile, filter, grid_size) {
+                cells.insert(cell.as_ivec2());
+            } else {
+                remaining.push((tile_position, tile));
+            }
+        }
+
+        let rects = merge_cells(cells)
+            .into_iter()
+            .map(|rect| {
+                Rect::from_corners(
+                    rect.min.as_vec2() * grid_size,
+                    rect.max.as_vec2() * grid_size,
+                )
+            })
+            .collect();
+        (rects, remaining)
+    }
+}
+
+/// Returns `true` if the only collision shape of this tile matching `filter` is an
+/// unrotated rectangle covering the whole tile.
+fn is_full_tile_rect(tile: &Tile, filter: &TiledNameFilter, grid_size: Vec2) -> bool {
+    let Some(collision) = &tile.collision else {
+        return false;
+    };
+    let mut objects = collision
+        .object_data()
+        .iter()
+        .filter(|object| filter.contains(&object.name));
+    match (objects.next(), objects.next()) {
+        (Some(object), None) => {
+            object.x == 0.
+                && object.y == 0.
+                && object.rotation == 0.
+                && matches!(
+                    object.shape,
+                    tiled::ObjectShape::Rect { width, height }
+                        if width == grid_size.x && height == grid_size.y
+                )
+        }
+        _ => false,
+    }
+}
+
+/// Greedily merge grid cells into rectangles.
+///
+/// Cells are processed row-by-row: each rectangle is first extended along its row,
+/// then along the following rows as long as they fully contain the same span.
+/// Returned rectangles `max` corner is exclusive.
+fn merge_cells(mut cells: HashSet<IVec2>) -> Vec<IRect> {
+    let mut sorted = Vec::from_iter(cells.iter().copied());
+    sorted.sort_by_key(|cell| (cell.y, cell.x));
+
+    let mut rects = vec![];
+    for start in sorted {
+        if !cells.contains(&start) {
+            continue;
+        }
+        let mut end_x = start.x + 1;
+        while cells.contains(&IVec2::new(end_x, start.y)) {
+            end_x += 1;
+        }
+        let mut end_y = start.y + 1;
+        while (start.x..end_x).all(|x| cells.contains(&IVec2::new(x, end_y))) {
+            end_y += 1;
+        }
+        for y in start.y..end_y {
+            for x in start.x..end_x {
+                cells.remove(&IVec2::new(x, y));
+            }
+        }
+        rects.push(IRect::new(start.x, start.y, end_x, end_y));
+    }
+    rects
+}
+
+/// Remove tiles which have a matching polygon object: a triangle or a quad whose
+/// center is within one tile width of the center of a tile with collision shapes.
+///
+/// Only polygon objects from a layer matching `layer_filter` and whose name matches
+/// `object_filter` are considered. When several tiles are candidates, only the closest one is matched.
+fn remove_tiles_matching_polygon_objects(
+    tiled_map: &TiledMap,
+    tiles: &mut Vec<(Vec2, Tile)>,
+    layer_filter: &TiledNameFilter,
+    object_filter: &TiledNameFilter,
+) {
+    let grid_size = get_grid_size(&tiled_map.map);
+    // Tilemaps are offset by half a tile from the layer origin (see loader::load_tiles_layer())
+    let half_tile = Vec2::new(grid_size.x / 2., grid_size.y / 2.);
+
+    // Index tiles with collision shapes by position, using buckets at least as large as a tile:
+    // tiles within one tile width of a point are always in the 3x3 buckets around this point
+    let bucket_size = grid_size.x.max(grid_size.y);
+    let bucket = |position: Vec2| (position / bucket_size).floor().as_ivec2();
+    let mut buckets: HashMap<IVec2, Vec<usize>> = HashMap::default();
+    for (index, (tile_coords, tile)) in tiles.iter().enumerate() {
+        if tile.collision.is_some() {
+            buckets
+                .entry(bucket(*tile_coords + half_tile))
+                .or_default()
+                .push(index);
+        }
+    }
+
+    let mut matched = vec![false; tiles.len()];
+    let mut to_process = Vec::from_iter(tiled_map.map.layers());
+    while let Some(layer) = to_process.pop() {
+        let objects_layer = match layer.layer_type() {
+            tiled::LayerType::Group(group) => {
+                to_process.extend(group.layers());
+                continue;
+            }
+            tiled::LayerType::Objects(objects_layer) if layer_filter.contains(&layer.name) => {
+                objects_layer
+            }
+            _ => continue,
+        };
+        for object in objects_layer.objects() {
+            if !object_filter.contains(&object.name) {
+                continue;
+            }
+            let tiled::ObjectShape::Polygon { points } = &object.shape else {
+                continue;
+            };
+            if !(3..=4).contains(&points.len()) {
+                continue;
+            }
+
+            // Object rotation is clockwise and in degrees, around object origin
+            let rotation = Vec2::from_angle(object.rotation.to_radians());
+            let centroid = points
+                .iter()
+                .fold(Vec2::ZERO, |acc, (x, y)| acc + Vec2::new(*x, *y))
+                / points.len() as f32;
+            let center = from_tiled_position_to_world_space(
+                tiled_map,
+                Vec2::new(object.x, object.y) + rotation.rotate(centroid),
+            );
+
+            let center_bucket = bucket(center);
+            if let Some((index, distance)) = (-1..=1)
+                .flat_map(|x| (-1..=1).map(move |y| center_bucket + IVec2::new(x, y)))
+                .filter_map(|b| buckets.get(&b))
+                .flatten()
+                .map(|index| (*index, (tiles[*index].0 + half_tile).distance(center)))
+                .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            {
+                if distance < grid_size.x {
+                    matched[index] = true;
+                }
+            }
+        }
+    }
+
+    let mut matched = matched.into_iter();
+    tiles.retain(|_| !matched.next().unwrap_or_default());
 }
 
 /// Spawn informations about a collider
@@ -105,24 +369,111 @@ pub struct TiledColliderSpawnInfos {
     pub transform: Transform,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub(super) fn spawn_colliders<T: super::TiledPhysicsBackend>(
-    backend: &T,
+    settings: &super::TiledPhysicsSettings<T>,
     parent: Entity,
     commands: &mut Commands,
     tiled_map: &TiledMap,
     names: &TiledName,
     collider: &TiledCollider,
+    sensor: bool,
+    scale: Vec2,
 ) {
+    let backend = &settings.backend;
     for spawn_infos in
         backend.spawn_colliders(commands, tiled_map, &TiledNameFilter::from(names), collider)
     {
+        if sensor {
+            backend.make_sensor(commands, spawn_infos.entity);
+        }
+        if settings.friction.is_some() || settings.restitution.is_some() {
+            backend.set_material(
+                commands,
+                spawn_infos.entity,
+                settings.friction,
+                settings.restitution,
+            );
+        }
         commands
             .entity(spawn_infos.entity)
             .insert((
                 TiledColliderMarker,
                 Name::new(format!("Collider: {}", spawn_infos.name)),
-                spawn_infos.transform,
+                Transform::from_scale(scale.extend(1.)) * spawn_infos.transform,
             ))
             .set_parent(parent);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merge_cells_builds_maximal_rectangles() {
+        // Two full rows of 3 cells, plus a single cell on top of them
+        let cells = HashSet::from_iter([
+            IVec2::new(0, 0),
+            IVec2::new(1, 0),
+            IVec2::new(2, 0),
+            IVec2::new(0, 1),
+            IVec2::new(1, 1),
+            IVec2::new(2, 1),
+            IVec2::new(5, 2),
+        ]);
+        let mut rects = merge_cells(cells);
+        rects.sort_by_key(|rect| (rect.min.y, rect.min.x));
+        assert_eq!(rects, vec![IRect::new(0, 0, 3, 2), IRect::new(5, 2, 6, 3)]);
+    }
+
+    #[test]
+    fn polygon_objects_replace_tiles_shapes() {
+        let (app, handle) = crate::test_utils::load_map("maps/orthogonal/slopes.tmx");
+        let tiled_map = app
+            .world()
+            .resource::<Assets<TiledMap>>()
+            .get(&handle)
+            .expect("map should be loaded");
+        let remaining_tiles = |collider: TiledCollider| {
+            let mut x: Vec<f32> = collider
+                .get_tiles(tiled_map)
+                .iter()
+                .map(|(position, _)| position.x)
+                .collect();
+            x.sort_by(f32::total_cmp);
+            x
+        };
+        let names =
+            |names: &[&str]| TiledName::Names(names.iter().map(|n| n.to_string()).collect());
+
+        let collider = TiledCollider::from_tiles_layer(0);
+        assert_eq!(remaining_tiles(collider.clone()), vec![0., 32., 64., 96.]);
+        // All polygons are named "slope", except the one on the third tile
+        assert_eq!(
+            remaining_tiles(
+                collider
+                    .clone()
+                    .with_polygon_objects_for_tile_shapes(TiledName::All, names(&["slope"]))
+            ),
+            vec![32., 64.]
+        );
+        // Polygon on the last tile belongs to the "decoration" layer: when this layer is
+        // filtered out, the last tile keeps its own shape
+        assert_eq!(
+            remaining_tiles(
+                collider
+                    .clone()
+                    .with_polygon_objects_for_tile_shapes(names(&["slopes"]), TiledName::All)
+            ),
+            vec![32., 96.]
+        );
+        assert_eq!(
+            remaining_tiles(
+                collider
+                    .with_polygon_objects_for_tile_shapes(names(&["decoration"]), TiledName::All)
+            ),
+            vec![0., 32., 64.]
+        );
+    }
+}
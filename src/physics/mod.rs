@@ -49,6 +49,10 @@ pub trait TiledPhysicsBackend:
     /// collider and return informations about it.
     /// In case the provided [TiledCollider] is not supported, it should
     /// not spawn anything and return an empty [Vec].
+    ///
+    /// Colliders are spawned as children of their source (ie. the object or tiles layer [Entity]):
+    /// object shapes points must be kept relative to the object (see [TiledObjectShape]),
+    /// world-space position will be computed from the object [Transform].
     fn spawn_colliders(
         &self,
         commands: &mut Commands,
@@ -56,10 +60,31 @@ pub trait TiledPhysicsBackend:
         filter: &TiledNameFilter,
         collider: &TiledCollider,
     ) -> Vec<TiledColliderSpawnInfos>;
+
+    /// Function responsible for turning a collider into a sensor
+    ///
+    /// Called for colliders spawned by [TiledPhysicsBackend::spawn_colliders] for objects
+    /// whose class matches [TiledPhysicsSettings::sensor_class].
+    /// Default implementation does nothing.
+    fn make_sensor(&self, _commands: &mut Commands, _collider_entity: Entity) {}
+
+    /// Function responsible for setting a collider friction and restitution coefficients
+    ///
+    /// Called for colliders spawned by [TiledPhysicsBackend::spawn_colliders] when either
+    /// [TiledPhysicsSettings::friction] or [TiledPhysicsSettings::restitution] is set.
+    /// Default implementation does nothing.
+    fn set_material(
+        &self,
+        _commands: &mut Commands,
+        _collider_entity: Entity,
+        _friction: Option<f32>,
+        _restitution: Option<f32>,
+    ) {
+    }
 }
 
 /// Physics related settings.
-#[derive(Component, Default, Reflect, Clone, Debug)]
+#[derive(Component, Reflect, Clone, Debug)]
 #[reflect(Component, Default, Debug)]
 pub struct TiledPhysicsSettings<T: TiledPhysicsBackend> {
     /// Specify which Tiled object to add colliders for using their layer name.
@@ -82,10 +107,58 @@ pub struct TiledPhysicsSettings<T: TiledPhysicsBackend> {
     /// Colliders will be automatically added for all tiles collision objects whose name matches this filter.
     /// By default, we add colliders for all collision objects.
     pub tiles_objects_filter: TiledName,
+    /// Use polygon objects as collision shape for the tiles they are placed on.
+    ///
+    /// This is typically useful for slopes: when enabled, a polygon object with three or four vertices
+    /// whose center is within one tile width of a tile center is considered to be this tile collision shape.
+    /// The tile own collision objects are then ignored and only the polygon object collider is spawned.
+    /// Only polygon objects matching [TiledPhysicsSettings::objects_layer_filter] and
+    /// [TiledPhysicsSettings::objects_filter] are used, since other objects do not get a collider.
+    ///
+    /// Disabled by default.
+    pub use_polygon_objects_for_tile_shapes: bool,
+    /// Tiled class of the objects whose colliders should be sensors.
+    ///
+    /// Colliders spawned for objects with this class are turned into sensors using
+    /// [TiledPhysicsBackend::make_sensor]: they detect intersections but do not produce contacts.
+    /// By default, no collider is a sensor.
+    pub sensor_class: Option<String>,
+    /// Merge all tiles colliders of a tiles layer into a single collider.
+    ///
+    /// Reduces the number of colliders, which improves performances. When disabled, each tile
+    /// collision shape gets its own collider, still attached to the tiles layer [Entity].
+    ///
+    /// Enabled by default.
+    pub merge_tile_colliders: bool,
+    /// Friction coefficient of all the spawned colliders.
+    ///
+    /// By default, we use the physics engine default friction.
+    pub friction: Option<f32>,
+    /// Restitution coefficient of all the spawned colliders.
+    ///
+    /// By default, we use the physics engine default restitution.
+    pub restitution: Option<f32>,
     /// Physics backend to use for adding colliders.
     pub backend: T,
 }
 
+impl<T: TiledPhysicsBackend> Default for TiledPhysicsSettings<T> {
+    fn default() -> Self {
+        Self {
+            objects_layer_filter: TiledName::default(),
+            objects_filter: TiledName::default(),
+            tiles_layer_filter: TiledName::default(),
+            tiles_objects_filter: TiledName::default(),
+            use_polygon_objects_for_tile_shapes: false,
+            sensor_class: None,
+            merge_tile_colliders: true,
+            friction: None,
+            restitution: None,
+            backend: T::default(),
+        }
+    }
+}
+
 /// Physics plugin.
 ///
 /// Must be added to your app in order to automatically spawn physics colliders using the provided [TiledPhysicsBackend].
@@ -116,9 +189,12 @@ impl<T: TiledPhysicsBackend> Plugin for TiledPhysicsPlugin<T> {
                     collider_from_object::<T>,
                 )
                     .chain()
-                    .after(crate::map::process_loaded_maps),
+                    .after(crate::map::loader::spawn_pending_tiles),
             )
             .add_systems(PostUpdate, update_settings::<T>);
+
+        #[cfg(feature = "rapier")]
+        app.register_type::<rapier::TiledOneWayPlatform>();
     }
 }
 
@@ -175,14 +251,17 @@ fn collider_from_tiles_layer<T: TiledPhysicsBackend>(
     mut layer_event: EventReader<TiledLayerCreated>,
     mut commands: Commands,
     map_asset: Res<Assets<TiledMap>>,
-    maps_query: Query<&TiledPhysicsSettings<T>, With<TiledMapMarker>>,
+    maps_query: Query<
+        (&TiledPhysicsSettings<T>, Option<&TiledMapTileSizeOverride>),
+        With<TiledMapMarker>,
+    >,
 ) {
     for ev in layer_event.read() {
         debug!(
             "map entity = {:?}, layer entity = {:?}",
             ev.map.entity, ev.entity
         );
-        let settings = maps_query
+        let (settings, tile_size_override) = maps_query
             .get(ev.map.entity)
             .expect("TiledPhysicsSettings<T> component should be on map entity");
         let Some(tiled_map) = ev.map.get_map_asset(&map_asset) else {
@@ -196,13 +275,25 @@ fn collider_from_tiles_layer<T: TiledPhysicsBackend>(
         };
 
         if TiledNameFilter::from(&settings.tiles_layer_filter).contains(&layer.name) {
+            let mut collider = TiledCollider::from_tiles_layer(ev.id)
+                .with_merge_tile_colliders(settings.merge_tile_colliders);
+            if settings.use_polygon_objects_for_tile_shapes {
+                // Only use polygon objects which actually get a collider
+                collider = collider.with_polygon_objects_for_tile_shapes(
+                    settings.objects_layer_filter.clone(),
+                    settings.objects_filter.clone(),
+                );
+            }
             collider::spawn_colliders(
-                &settings.backend,
+                settings,
                 ev.entity,
                 &mut commands,
                 tiled_map,
                 &settings.tiles_objects_filter,
-                &TiledCollider::from_tiles_layer(ev.id),
+                &collider,
+                false,
+                // Follow the tiles scale when their size is overridden
+                tile_size_override.map_or(Vec2::ONE, |o| o.map_scale(&tiled_map.map)),
             );
         }
     }
@@ -233,7 +324,7 @@ fn collider_from_object<T: TiledPhysicsBackend>(
             && TiledNameFilter::from(&settings.objects_filter).contains(&object.name)
         {
             collider::spawn_colliders(
-                &settings.backend,
+                settings,
                 ev.entity,
                 &mut commands,
                 tiled_map,
@@ -242,6 +333,12 @@ fn collider_from_object<T: TiledPhysicsBackend>(
                     None => &TiledName::All,
                 },
                 &TiledCollider::from_object(ev.layer.id, ev.id),
+                settings
+                    .sensor_class
+                    .as_ref()
+                    .is_some_and(|class| *class == object.user_type),
+                // Objects Transform already follows the tiles scale
+                Vec2::ONE,
             );
         }
     }
@@ -2,11 +2,11 @@
 //!
 //! Only available when the `rapier` feature is enabled.
 
-use bevy::prelude::*;
+use bevy::{ecs::system::SystemParam, prelude::*};
 use bevy_ecs_tilemap::map::TilemapGridSize;
 use bevy_rapier2d::{
     prelude::*,
-    rapier::prelude::{Isometry, Real, SharedShape},
+    rapier::prelude::{Isometry, Real, SharedShape, Vector},
 };
 use tiled::{ObjectLayerData, ObjectShape};
 
@@ -22,9 +22,62 @@ use crate::prelude::*;
 /// App::new()
 ///     .add_plugins(TiledPhysicsPlugin::<TiledPhysicsRapierBackend>::default());
 /// ```
-#[derive(Default, Reflect, Copy, Clone, Debug)]
+#[derive(Default, Reflect, Clone, Debug)]
 #[reflect(Default, Debug)]
-pub struct TiledPhysicsRapierBackend;
+pub struct TiledPhysicsRapierBackend {
+    /// Names of the tiles layers whose colliders should behave as one-way platforms.
+    ///
+    /// Bodies can go through these colliders from below but will land on them from above.
+    /// Colliders spawned for these layers get a [TiledOneWayPlatform] marker, which requires
+    /// using [TiledOneWayPlatformHooks] as Rapier physics hooks:
+    ///
+    /// ```rust,no_run
+    /// use bevy::prelude::*;
+    /// use bevy_ecs_tiled::prelude::*;
+    /// use bevy_rapier2d::prelude::*;
+    ///
+    /// App::new()
+    ///     .add_plugins(TiledPhysicsPlugin::<TiledPhysicsRapierBackend>::default())
+    ///     .add_plugins(RapierPhysicsPlugin::<TiledOneWayPlatformHooks>::pixels_per_meter(100.0));
+    /// ```
+    pub one_way_platform_layer_names: Vec<String>,
+}
+
+/// Marker [Component] for colliders which should behave as one-way platforms.
+///
+/// See [TiledPhysicsRapierBackend::one_way_platform_layer_names].
+#[derive(Component, Default, Reflect, Copy, Clone, Debug)]
+#[reflect(Component, Default, Debug)]
+pub struct TiledOneWayPlatform;
+
+/// Rapier physics hooks implementing one-way platforms.
+///
+/// Contacts with a [TiledOneWayPlatform] collider are only kept when the other body
+/// comes from above the platform.
+#[derive(SystemParam)]
+#[allow(missing_debug_implementations)]
+pub struct TiledOneWayPlatformHooks<'w, 's> {
+    platforms: Query<'w, 's, (), With<TiledOneWayPlatform>>,
+}
+
+impl BevyPhysicsHooks for TiledOneWayPlatformHooks<'_, '_> {
+    fn modify_solver_contacts(&self, context: ContactModificationContextView) {
+        // Allowed normal is expressed in the first collider local space
+        if self.platforms.contains(context.collider1()) {
+            context
+                .raw
+                .update_as_oneway_platform(&Vector::y(), ONE_WAY_PLATFORM_ALLOWED_ANGLE);
+        } else if self.platforms.contains(context.collider2()) {
+            context
+                .raw
+                .update_as_oneway_platform(&-Vector::y(), ONE_WAY_PLATFORM_ALLOWED_ANGLE);
+        }
+    }
+}
+
+/// Maximum angle (in radians) between a contact normal and the platform up direction
+/// for the contact to be kept.
+const ONE_WAY_PLATFORM_ALLOWED_ANGLE: Real = 0.1;
 
 impl TiledPhysicsBackend for TiledPhysicsRapierBackend {
     fn spawn_colliders(
@@ -83,10 +136,18 @@ impl TiledPhysicsBackend for TiledPhysicsRapierBackend {
                 }
                 .unwrap_or_default()
             }
-            TiledCollider::TilesLayer { layer_id: _ } => {
-                let mut composables = vec![];
+            TiledCollider::TilesLayer { .. } => {
+                let (rects, tiles) = collider.get_merged_tiles(tiled_map, filter);
+                let mut composables = Vec::from_iter(rects.into_iter().map(|rect| {
+                    let center = rect.center();
+                    let half_size = rect.half_size();
+                    (
+                        Isometry::<Real>::new(center.into(), 0.),
+                        SharedShape::cuboid(half_size.x, half_size.y),
+                    )
+                }));
                 let mut spawn_infos = vec![];
-                for (tile_position, tile) in collider.get_tiles(tiled_map) {
+                for (tile_position, tile) in tiles {
                     if let Some(collision) = &tile.collision {
                         compose_tiles(
                             commands,
@@ -99,6 +160,21 @@ impl TiledPhysicsBackend for TiledPhysicsRapierBackend {
                         );
                     }
                 }
+                if !collider.merge_tile_colliders() {
+                    for (iso, shape) in composables.drain(..) {
+                        let collider: Collider = shape.into();
+                        spawn_infos.push(TiledColliderSpawnInfos {
+                            name: "Rapier[Tile]".to_string(),
+                            entity: commands.spawn(collider).id(),
+                            transform: Transform::from_xyz(
+                                iso.translation.vector.x,
+                                iso.translation.vector.y,
+                                0.,
+                            )
+                            .with_rotation(Quat::from_rotation_z(iso.rotation.angle())),
+                        });
+                    }
+                }
                 if !composables.is_empty() {
                     let collider: Collider = SharedShape::compound(composables).into();
                     spawn_infos.push(TiledColliderSpawnInfos {
@@ -107,10 +183,43 @@ impl TiledPhysicsBackend for TiledPhysicsRapierBackend {
                         transform: Transform::default(),
                     });
                 }
+                if collider
+                    .get_layer(tiled_map)
+                    .is_some_and(|layer| self.one_way_platform_layer_names.contains(&layer.name))
+                {
+                    for info in &spawn_infos {
+                        commands
+                            .entity(info.entity)
+                            .insert((TiledOneWayPlatform, ActiveHooks::MODIFY_SOLVER_CONTACTS));
+                    }
+                }
                 spawn_infos
             }
         }
     }
+
+    fn make_sensor(&self, commands: &mut Commands, collider_entity: Entity) {
+        commands.entity(collider_entity).insert(Sensor);
+    }
+
+    fn set_material(
+        &self,
+        commands: &mut Commands,
+        collider_entity: Entity,
+        friction: Option<f32>,
+        restitution: Option<f32>,
+    ) {
+        if let Some(friction) = friction {
+            commands
+                .entity(collider_entity)
+                .insert(Friction::coefficient(friction));
+        }
+        if let Some(restitution) = restitution {
+            commands
+                .entity(collider_entity)
+                .insert(Restitution::coefficient(restitution));
+        }
+    }
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -169,7 +278,9 @@ fn get_position_and_shape(shape: &ObjectShape) -> Option<(Vec2, SharedShape, boo
             Some((pos, shape, true))
         }
         ObjectShape::Ellipse { width, height } => {
-            let shape = if width > height {
+            let shape = if width == height {
+                SharedShape::ball(width / 2.)
+            } else if width > height {
                 SharedShape::capsule(
                     Vec2::new((-width + height) / 2., 0.).into(),
                     Vec2::new((width - height) / 2., 0.).into(),
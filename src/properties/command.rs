@@ -2,12 +2,15 @@ use super::load::DeserializedProperties;
 use bevy::ecs::reflect::ReflectBundle;
 use bevy::ecs::system::EntityCommands;
 use bevy::ecs::world::Command;
-use bevy::prelude::{AppTypeRegistry, Entity, ReflectComponent, ReflectResource, World};
+use bevy::prelude::{
+    AppTypeRegistry, Entity, ReflectComponent, ReflectDefault, ReflectResource, World,
+};
 use bevy::reflect::{PartialReflect, TypeRegistry};
 use std::ops::Deref;
 
 pub(crate) trait PropertiesCommandExt {
     fn insert_properties(&mut self, properties: DeserializedProperties) -> &mut Self;
+    fn insert_class(&mut self, class: &Option<String>) -> &mut Self;
 }
 
 impl PropertiesCommandExt for EntityCommands<'_> {
@@ -18,6 +21,64 @@ impl PropertiesCommandExt for EntityCommands<'_> {
 
         self
     }
+
+    fn insert_class(&mut self, class: &Option<String>) -> &mut Self {
+        let Some(class) = class.as_ref().filter(|c| !c.is_empty()) else {
+            return self;
+        };
+        let entity = self.id();
+        self.commands().queue(InsertClass {
+            entity,
+            class: class.clone(),
+        });
+
+        self
+    }
+}
+
+/// Insert a default instance of the [Component] whose type path matches the given Tiled class.
+pub(crate) struct InsertClass {
+    pub(crate) entity: Entity,
+    pub(crate) class: String,
+}
+
+impl Command for InsertClass {
+    fn apply(self, world: &mut World) {
+        let binding = world.get_resource::<AppTypeRegistry>().unwrap().clone();
+        let type_registry = binding.0.read();
+
+        let Some(type_registration) = type_registry
+            .get_with_type_path(&self.class)
+            .or_else(|| type_registry.get_with_short_type_path(&self.class))
+        else {
+            // Tiled classes can be used for other purposes: do not complain too loudly
+            bevy::log::debug!(
+                "Tiled class `{}` is not registered in the TypeRegistry, skip it",
+                self.class
+            );
+            return;
+        };
+        let (Some(reflect_component), Some(reflect_default)) = (
+            type_registration.data::<ReflectComponent>(),
+            type_registration.data::<ReflectDefault>(),
+        ) else {
+            bevy::log::warn!(
+                "Tiled class `{}` should be registered as a Component implementing Default",
+                self.class
+            );
+            return;
+        };
+
+        let component = reflect_default.default();
+        let Ok(mut entity) = world.get_entity_mut(self.entity) else {
+            return;
+        };
+        reflect_component.insert(
+            &mut entity,
+            component.as_partial_reflect(),
+            type_registry.deref(),
+        );
+    }
 }
 
 pub(crate) struct InsertProperties {
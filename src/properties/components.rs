@@ -0,0 +1,103 @@
+//! This module contains all properties [Component]s definition.
+
+use bevy::{prelude::*, reflect::PartialReflect, utils::HashMap};
+
+/// Custom properties of a Tiled map which use a standard type.
+///
+/// Inserted on the map [Entity], alongside the [TiledMapMarker](crate::prelude::TiledMapMarker) marker.
+/// Properties using a custom type are not stored here: they are directly inserted
+/// on the map [Entity] as [Component]s (or as [Resource]s).
+///
+/// Values are stored using the following types:
+/// - `bool` properties as [bool]
+/// - `int` properties as [i32]
+/// - `float` properties as [f32]
+/// - `string` and `file` properties as [String]
+/// - `color` properties as [Color]
+/// - `object` properties as an [`Option<Entity>`], which is [None] if the object was not spawned
+///
+/// Values are deserialized through the [AppTypeRegistry], the same way as properties using a
+/// custom type, but they are not visible through reflection: use [TiledMapProperties::get()].
+///
+/// Example:
+/// ```rust,no_run
+/// use bevy::prelude::*;
+/// use bevy_ecs_tiled::prelude::*;
+///
+/// fn print_level_name(query: Query<&TiledMapProperties, With<TiledMapMarker>>) {
+///     for properties in query.iter() {
+///         if let Some(name) = properties.get::<String>("level_name") {
+///             info!("Entering level {name}");
+///         }
+///     }
+/// }
+/// ```
+#[derive(Component, Default, Reflect, Debug)]
+#[reflect(Component, Default, Debug)]
+pub struct TiledMapProperties(#[reflect(ignore)] pub HashMap<String, Box<dyn PartialReflect>>);
+
+impl Clone for TiledMapProperties {
+    fn clone(&self) -> Self {
+        Self(
+            self.0
+                .iter()
+                .map(|(name, value)| (name.clone(), value.clone_value()))
+                .collect(),
+        )
+    }
+}
+
+impl TiledMapProperties {
+    /// Retrieve the value of a property, if it exists and has the given type.
+    pub fn get<T: FromReflect>(&self, name: &str) -> Option<T> {
+        self.0
+            .get(name)
+            .and_then(|value| T::from_reflect(value.as_ref()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{prelude::*, test_utils::spawn_map};
+
+    #[test]
+    fn map_standard_properties() {
+        let (app, map_entity) = spawn_map("maps/orthogonal/classes.tmx", ());
+        assert!(app
+            .world()
+            .resource::<AppTypeRegistry>()
+            .read()
+            .contains(std::any::TypeId::of::<TiledMapProperties>()));
+
+        let properties = app
+            .world()
+            .get::<TiledMapProperties>(map_entity)
+            .expect("map properties should be inserted")
+            .clone();
+        assert_eq!(properties.get::<bool>("boss"), Some(true));
+        assert_eq!(properties.get::<i32>("level"), Some(3));
+        assert_eq!(properties.get::<f32>("gravity"), Some(9.5));
+        assert_eq!(
+            properties.get::<String>("title"),
+            Some(String::from("Dungeon"))
+        );
+        assert_eq!(
+            properties.get::<Color>("tint"),
+            Some(Color::srgba_u8(255, 0, 0, 255))
+        );
+        // Object references are replaced by the object entity
+        let door = app
+            .world()
+            .get::<TiledMapStorage>(map_entity)
+            .unwrap()
+            .objects[&1];
+        assert_eq!(
+            properties.get::<Option<Entity>>("entrance"),
+            Some(Some(door))
+        );
+        // Values are only retrieved using their own type
+        assert_eq!(properties.get::<String>("level"), None);
+        assert_eq!(properties.get::<bool>("missing"), None);
+    }
+}
@@ -6,13 +6,128 @@ use bevy::reflect::{
     NamedField, Reflect, ReflectMut, ReflectRef, TypeInfo, TypeRegistration, TypeRegistry,
     UnnamedField, VariantInfo, VariantType,
 };
-use bevy::utils::HashMap;
-use std::path::PathBuf;
+use bevy::utils::{HashMap, HashSet};
+use std::{any::TypeId, path::PathBuf};
 use tiled::{LayerType, Properties, PropertyValue, TileId};
 
+use crate::prelude::{TiledMapProperties, TiledMapStorage};
+use crate::properties::types_json::{FieldType, Member, TypeData, TypeExport};
+
+/// Classes default values, read from a Tiled property types JSON file.
+///
+/// Tiled does not store class members which still have their default value:
+/// we use these definitions to fill them before deserializing properties.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ClassDefaults(HashMap<String, Vec<Member>>);
+
+/// Parse a Tiled color, either `#AARRGGBB` or `#RRGGBB`.
+fn parse_color(color: &str) -> Option<tiled::Color> {
+    let hex = color.strip_prefix('#')?;
+    let component = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+    match hex.len() {
+        8 => Some(tiled::Color {
+            alpha: component(0)?,
+            red: component(2)?,
+            green: component(4)?,
+            blue: component(6)?,
+        }),
+        6 => Some(tiled::Color {
+            alpha: 0xff,
+            red: component(0)?,
+            green: component(2)?,
+            blue: component(4)?,
+        }),
+        _ => None,
+    }
+}
+
+impl ClassDefaults {
+    /// Parse a Tiled property types JSON file (ie. the `propertyTypes` exported by Tiled).
+    pub(crate) fn from_json(bytes: &[u8]) -> serde_json::Result<Self> {
+        let types: Vec<TypeExport> = serde_json::from_slice(bytes)?;
+        Ok(Self(
+            types
+                .into_iter()
+                .filter_map(|t| match t.type_data {
+                    TypeData::Class(class) => Some((t.name, class.members)),
+                    TypeData::Enum(_) => None,
+                })
+                .collect(),
+        ))
+    }
+
+    /// Recursively add missing class members to all class properties.
+    fn fill(&self, properties: &mut Properties) {
+        for property in properties.values_mut() {
+            if let PropertyValue::ClassValue {
+                property_type,
+                properties,
+            } = property
+            {
+                self.fill_class(property_type, properties);
+            }
+        }
+    }
+
+    fn fill_class(&self, class: &str, properties: &mut Properties) {
+        self.fill(properties);
+        let Some(members) = self.0.get(class) else {
+            return;
+        };
+        for member in members {
+            if properties.contains_key(&member.name) {
+                continue;
+            }
+            if let Some(value) = self.member_default(member) {
+                properties.insert(member.name.clone(), value);
+            }
+        }
+    }
+
+    fn member_default(&self, member: &Member) -> Option<PropertyValue> {
+        let value = &member.value;
+        Some(match member.type_field {
+            FieldType::Bool => PropertyValue::BoolValue(value.as_bool()?),
+            FieldType::Int => PropertyValue::IntValue(value.as_i64()?.try_into().ok()?),
+            FieldType::Float => PropertyValue::FloatValue(value.as_f64()? as f32),
+            FieldType::String => PropertyValue::StringValue(value.as_str()?.to_string()),
+            FieldType::File => PropertyValue::FileValue(value.as_str()?.to_string()),
+            FieldType::Color => PropertyValue::ColorValue(parse_color(value.as_str()?)?),
+            FieldType::Object => PropertyValue::ObjectValue(value.as_u64()?.try_into().ok()?),
+            FieldType::Class => {
+                let property_type = member.property_type.clone()?;
+                let mut properties = Properties::new();
+                if let Some(fields) = value.as_object() {
+                    let nested = self.0.get(&property_type);
+                    for (name, field_value) in fields {
+                        let Some(field) = nested.and_then(|m| m.iter().find(|m| &m.name == name))
+                        else {
+                            continue;
+                        };
+                        let field = Member {
+                            value: field_value.clone(),
+                            ..field.clone()
+                        };
+                        if let Some(v) = self.member_default(&field) {
+                            properties.insert(name.clone(), v);
+                        }
+                    }
+                }
+                self.fill_class(&property_type, &mut properties);
+                PropertyValue::ClassValue {
+                    property_type,
+                    properties,
+                }
+            }
+        })
+    }
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct DeserializedMapProperties<const HYDRATED: bool = false> {
     pub(crate) map: DeserializedProperties,
+    /// Map properties using a standard type
+    pub(crate) map_standard: TiledMapProperties,
     pub(crate) layers: HashMap<u32, DeserializedProperties>,
     pub(crate) tiles: HashMap<String, HashMap<TileId, DeserializedProperties>>,
     pub(crate) objects: HashMap<u32, DeserializedProperties>,
@@ -23,8 +138,19 @@ impl DeserializedMapProperties<false> {
         map: &tiled::Map,
         registry: &TypeRegistry,
         load_context: &mut LoadContext<'_>,
+        used_tile_ids: Option<&HashMap<usize, HashSet<TileId>>>,
+        class_defaults: Option<&ClassDefaults>,
     ) -> Self {
-        let map_props = DeserializedProperties::load(&map.properties, registry, load_context, true);
+        let map_props = DeserializedProperties::load(
+            &map.properties,
+            registry,
+            load_context,
+            class_defaults,
+            true,
+        );
+
+        let map_standard =
+            TiledMapProperties(deserialize_standard_properties(&map.properties, registry));
 
         let mut objects = HashMap::new();
         let mut layers = HashMap::new();
@@ -32,7 +158,13 @@ impl DeserializedMapProperties<false> {
         while let Some(layer) = to_process.pop() {
             layers.insert(
                 layer.id(),
-                DeserializedProperties::load(&layer.properties, registry, load_context, false),
+                DeserializedProperties::load(
+                    &layer.properties,
+                    registry,
+                    load_context,
+                    class_defaults,
+                    false,
+                ),
             );
             match layer.layer_type() {
                 LayerType::Objects(object) => {
@@ -43,6 +175,7 @@ impl DeserializedMapProperties<false> {
                                 &object.properties,
                                 registry,
                                 load_context,
+                                class_defaults,
                                 false,
                             ),
                         );
@@ -58,10 +191,16 @@ impl DeserializedMapProperties<false> {
         let tiles = map
             .tilesets()
             .iter()
-            .map(|s| {
+            .enumerate()
+            .map(|(tileset_index, s)| {
                 (
                     s.name.clone(),
                     s.tiles()
+                        .filter(|(id, _)| {
+                            used_tile_ids.is_none_or(|used| {
+                                used.get(&tileset_index).is_some_and(|ids| ids.contains(id))
+                            })
+                        })
                         .map(|(id, t)| {
                             (
                                 id,
@@ -69,6 +208,7 @@ impl DeserializedMapProperties<false> {
                                     &t.properties,
                                     registry,
                                     load_context,
+                                    class_defaults,
                                     false,
                                 ),
                             )
@@ -80,17 +220,23 @@ impl DeserializedMapProperties<false> {
 
         Self {
             map: map_props,
+            map_standard,
             layers,
             tiles,
             objects,
         }
     }
 
-    pub(crate) fn hydrate(
-        mut self,
-        entity_map: &HashMap<u32, Entity>,
-    ) -> DeserializedMapProperties<true> {
+    pub(crate) fn hydrate(mut self, storage: &TiledMapStorage) -> DeserializedMapProperties<true> {
+        let entity_map = &storage.objects;
+        // Objects and layers which were filtered out are not spawned: there is nothing to hydrate
+        self.objects.retain(|id, _| entity_map.contains_key(id));
+        self.layers.retain(|id, _| storage.layers.contains_key(id));
+
         self.map.hydrate(entity_map);
+        for value in self.map_standard.0.values_mut() {
+            hydrate(value.as_mut(), entity_map);
+        }
         for (_, layer) in self.layers.iter_mut() {
             layer.hydrate(entity_map);
         }
@@ -105,6 +251,7 @@ impl DeserializedMapProperties<false> {
 
         DeserializedMapProperties::<true> {
             map: self.map,
+            map_standard: self.map_standard,
             layers: self.layers,
             tiles: self.tiles,
             objects: self.objects,
@@ -131,11 +278,17 @@ impl DeserializedProperties {
         properties: &tiled::Properties,
         registry: &TypeRegistry,
         load_cx: &mut LoadContext<'_>,
+        class_defaults: Option<&ClassDefaults>,
         resources_allowed: bool,
     ) -> Self {
         let mut props: Vec<Box<dyn PartialReflect>> = Vec::new();
 
-        for (name, property) in properties.clone() {
+        let mut properties = properties.clone();
+        if let Some(class_defaults) = class_defaults {
+            class_defaults.fill(&mut properties);
+        }
+
+        for (name, property) in properties {
             let (reg_name, reg) = match &property {
                 PropertyValue::ClassValue {
                     property_type,
@@ -569,6 +722,44 @@ impl DeserializedProperties {
     }
 }
 
+/// Deserialize properties using a standard type, ie. which are not a class.
+///
+/// Each value uses the type registered for its Tiled property type (see [TiledMapProperties]).
+/// Object references are left unhydrated.
+fn deserialize_standard_properties(
+    properties: &Properties,
+    registry: &TypeRegistry,
+) -> HashMap<String, Box<dyn PartialReflect>> {
+    let mut out = HashMap::new();
+    for (name, property) in properties {
+        use PropertyValue as PV;
+        let (type_id, property) = match property {
+            PV::BoolValue(_) => (TypeId::of::<bool>(), property.clone()),
+            PV::IntValue(_) => (TypeId::of::<i32>(), property.clone()),
+            PV::FloatValue(_) => (TypeId::of::<f32>(), property.clone()),
+            PV::StringValue(_) => (TypeId::of::<String>(), property.clone()),
+            PV::FileValue(file) => (TypeId::of::<String>(), PV::StringValue(file.clone())),
+            PV::ColorValue(_) => (TypeId::of::<Color>(), property.clone()),
+            PV::ObjectValue(_) => (TypeId::of::<Option<Entity>>(), property.clone()),
+            PV::ClassValue { .. } => continue,
+        };
+        let Some(reg) = registry.get(type_id) else {
+            bevy::log::error!("error deserializing property `{name}`: its type is not registered in the TypeRegistry.");
+            continue;
+        };
+        match DeserializedProperties::deserialize_property(property, reg, registry, &mut None, None)
+        {
+            Ok(value) => {
+                out.insert(name.clone(), value);
+            }
+            Err(e) => {
+                bevy::log::error!("error deserializing property `{name}`: {e}");
+            }
+        }
+    }
+    out
+}
+
 fn default_value_from_type_path(registry: &TypeRegistry, path: &str) -> Option<Box<dyn Reflect>> {
     registry
         .get_with_type_path(path)
@@ -581,14 +772,14 @@ fn object_ref(
 ) -> Option<Box<dyn PartialReflect>> {
     if obj.represents::<Entity>() {
         let obj = Entity::take_from_reflect(obj.clone_value()).unwrap();
-        if let Some(&e) = obj_entity_map.get(&obj.index()) {
-            Some(Box::new(e))
-        } else {
-            panic!(
-                "error hydrating properties: missing entity for object {}",
+        let entity = obj_entity_map.get(&obj.index()).copied().unwrap_or_else(|| {
+            warn!(
+                "error hydrating properties: missing entity for object {} (was its layer filtered out ?), using Entity::PLACEHOLDER",
                 obj.index()
             );
-        }
+            Entity::PLACEHOLDER
+        });
+        Some(Box::new(entity))
     } else if obj.represents::<Option<Entity>>() {
         // maybe the map get should panic actually
         Some(Box::new(
@@ -651,7 +842,7 @@ fn hydrate(object: &mut dyn PartialReflect, obj_entity_map: &HashMap<u32, Entity
             for i in 0..s.len() {
                 let (k, v) = s.get_at_mut(i).unwrap();
                 if object_ref(k, obj_entity_map).is_some() {
-                    panic!("Unable to hydrate a key in a map!");
+                    warn!("error hydrating properties: cannot hydrate object references used as map keys, leaving key {k:?} as is");
                 }
                 hydrate(v, obj_entity_map);
             }
@@ -697,6 +888,114 @@ mod tests {
         }
     }
 
+    #[test]
+    fn fill_class_defaults() {
+        let class_defaults = ClassDefaults::from_json(
+            br##"[
+                {"id": 1, "name": "Inner", "type": "class", "useAs": ["property"], "color": "#ff000000", "drawFill": true, "members": [
+                    {"name": "flag", "type": "bool", "value": true}
+                ]},
+                {"id": 2, "name": "Outer", "type": "class", "useAs": ["property"], "color": "#ff000000", "drawFill": true, "members": [
+                    {"name": "speed", "type": "float", "value": 1.5},
+                    {"name": "tint", "type": "color", "value": "#80ff0000"},
+                    {"name": "inner", "type": "class", "propertyType": "Inner", "value": {}}
+                ]}
+            ]"##,
+        )
+        .unwrap();
+
+        let mut properties = Properties::from([(
+            "outer".to_string(),
+            PropertyValue::ClassValue {
+                property_type: "Outer".to_string(),
+                properties: Properties::from([(
+                    "speed".to_string(),
+                    PropertyValue::FloatValue(3.),
+                )]),
+            },
+        )]);
+        class_defaults.fill(&mut properties);
+
+        let PropertyValue::ClassValue { properties, .. } = &properties["outer"] else {
+            panic!("property should still be a class");
+        };
+        assert_eq!(properties["speed"], PropertyValue::FloatValue(3.));
+        assert_eq!(
+            properties["tint"],
+            PropertyValue::ColorValue(tiled::Color {
+                alpha: 0x80,
+                red: 0xff,
+                green: 0,
+                blue: 0,
+            })
+        );
+        assert_eq!(
+            properties["inner"],
+            PropertyValue::ClassValue {
+                property_type: "Inner".to_string(),
+                properties: Properties::from([(
+                    "flag".to_string(),
+                    PropertyValue::BoolValue(true)
+                )]),
+            }
+        );
+    }
+
+    #[test]
+    fn hydrate_missing_objects() {
+        #[derive(Reflect, Debug)]
+        struct Target {
+            required: Entity,
+            optional: Option<Entity>,
+        }
+
+        let spawned = Entity::from_raw(42);
+        let storage = TiledMapStorage {
+            objects: HashMap::from([(1, spawned)]),
+            ..default()
+        };
+        let target = |id: u32| -> Box<dyn PartialReflect> {
+            Box::new(Target {
+                required: Entity::from_raw(id),
+                optional: Some(Entity::from_raw(id)),
+            })
+        };
+        let properties = DeserializedMapProperties::<false> {
+            map: DeserializedProperties {
+                properties: vec![target(1), target(2)],
+            },
+            layers: HashMap::new(),
+            tiles: HashMap::new(),
+            // Object 3 was not spawned, for instance because its layer was filtered out
+            objects: HashMap::from([
+                (
+                    1,
+                    DeserializedProperties {
+                        properties: vec![target(2)],
+                    },
+                ),
+                (
+                    3,
+                    DeserializedProperties {
+                        properties: vec![target(1)],
+                    },
+                ),
+            ]),
+        }
+        .hydrate(&storage);
+
+        let get = |p: &DeserializedProperties, i: usize| {
+            Target::from_reflect(p.properties[i].as_ref()).unwrap()
+        };
+        let (spawned_target, missing_target) = (get(&properties.map, 0), get(&properties.map, 1));
+        assert_eq!(spawned_target.required, spawned);
+        assert_eq!(spawned_target.optional, Some(spawned));
+        assert_eq!(missing_target.required, Entity::PLACEHOLDER);
+        assert_eq!(missing_target.optional, None);
+        assert!(properties.objects.contains_key(&1));
+        assert!(!properties.objects.contains_key(&3));
+    }
+
     #[test]
     fn deserialize_simple_enum() {
         #[derive(Reflect, PartialEq, Debug)]
@@ -5,6 +5,12 @@
 //! See the [associated example](https://github.com/adrien-bon/bevy_ecs_tiled/blob/main/examples/user_properties.rs) or the [dedicated book section](https://adrien-bon.github.io/bevy_ecs_tiled/guides/properties.html) for more information.
 
 pub(crate) mod command;
+pub mod components;
 pub(crate) mod export;
 pub(crate) mod load;
 pub(crate) mod types_json;
+
+/// `bevy_ecs_tiled` properties related public exports
+pub mod prelude {
+    pub use super::components::*;
+}
@@ -26,7 +26,8 @@ impl<'a> tiled::ResourceReader for BytesResourceReader<'a, '_> {
 
     fn read_from(&mut self, path: &Path) -> std::result::Result<Self::Resource, Self::Error> {
         if let Some(extension) = path.extension() {
-            if extension == "tsx" {
+            // Object templates (`.tx` files) are read the same way as external tilesets
+            if extension == "tsx" || extension == "tx" {
                 let future = self.context.read_asset_bytes(path.to_path_buf());
                 let data = futures_lite::future::block_on(future)
                     .map_err(|err| IoError::new(ErrorKind::NotFound, err))?;
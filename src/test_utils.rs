@@ -0,0 +1,116 @@
+//! Helpers shared by unit tests to load and spawn maps and worlds from the `assets/` directory.
+
+use crate::{cache::TiledResourceCache, prelude::*};
+use bevy::{asset::AssetPlugin, prelude::*};
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+
+/// Maximum time to wait for an asset to load or a map to spawn before failing the test.
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Create an [App] which can load and spawn maps and worlds, without rendering them.
+pub(crate) fn test_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        AssetPlugin::default(),
+        ImagePlugin::default(),
+        TransformPlugin,
+        HierarchyPlugin,
+    ))
+    .insert_resource(TiledResourceCache::new())
+    .insert_resource(TiledMapPluginConfig {
+        tiled_types_export_file: None,
+    })
+    .init_asset::<TextureAtlasLayout>();
+    crate::map::build(&mut app);
+    crate::world::build(&mut app);
+    app
+}
+
+/// Update the [App] until `done` returns `true`.
+///
+/// Panics if it takes longer than [TIMEOUT], using `what` in the panic message.
+pub(crate) fn update_until(app: &mut App, what: &str, mut done: impl FnMut(&World) -> bool) {
+    let start = Instant::now();
+    loop {
+        app.update();
+        if done(app.world()) {
+            return;
+        }
+        assert!(start.elapsed() < TIMEOUT, "timed out waiting for {what}");
+        // Assets are loaded by other threads: let them run
+        thread::yield_now();
+    }
+}
+
+/// Load the map at given path, along with its dependencies.
+pub(crate) fn load_map(path: &'static str) -> (App, Handle<TiledMap>) {
+    let mut app = test_app();
+    let handle = app.world().resource::<AssetServer>().load(path);
+    update_until(&mut app, path, |world| {
+        world
+            .resource::<AssetServer>()
+            .is_loaded_with_dependencies(&handle)
+    });
+    (app, handle)
+}
+
+/// Spawn the map at given path along with given [Bundle] and run the app until it is spawned.
+///
+/// Also returns if the map [Entity] has been despawned (ie. by a [TiledMapPostSpawnHook]).
+pub(crate) fn spawn_map(path: &'static str, bundle: impl Bundle) -> (App, Entity) {
+    let mut app = test_app();
+    let map_entity = spawn_map_in(&mut app, path, bundle);
+    (app, map_entity)
+}
+
+/// Same as [spawn_map], using an existing [App].
+pub(crate) fn spawn_map_in(app: &mut App, path: &'static str, bundle: impl Bundle) -> Entity {
+    let handle = app.world().resource::<AssetServer>().load(path);
+    let map_entity = app.world_mut().spawn((TiledMapHandle(handle), bundle)).id();
+    update_until(app, path, |world| {
+        world
+            .get_entity(map_entity)
+            .map_or(true, |e| e.contains::<TiledMapMarker>())
+    });
+    // Let the systems running after the map spawn process its entities
+    app.update();
+    map_entity
+}
+
+/// Spawn the world at given path along with given [Bundle] and run the app until it is spawned.
+///
+/// Maps spawned by the world are given an extra update to be processed.
+pub(crate) fn spawn_world(path: &'static str, bundle: impl Bundle) -> (App, Entity) {
+    let mut app = test_app();
+    let world_entity = spawn_world_in(&mut app, path, bundle);
+    (app, world_entity)
+}
+
+/// Same as [spawn_world], using an existing [App].
+pub(crate) fn spawn_world_in(app: &mut App, path: &'static str, bundle: impl Bundle) -> Entity {
+    let handle = app.world().resource::<AssetServer>().load(path);
+    let world_entity = app
+        .world_mut()
+        .spawn((TiledWorldHandle(handle), bundle))
+        .id();
+    update_until(app, path, |world| {
+        world
+            .get_entity(world_entity)
+            .map_or(true, |e| e.contains::<TiledWorldMarker>())
+    });
+    // Let the maps spawned by world chunking be processed
+    app.update();
+    world_entity
+}
+
+/// Get a loaded [TiledMap] asset.
+pub(crate) fn map_asset<'a>(app: &'a App, handle: &Handle<TiledMap>) -> &'a TiledMap {
+    app.world()
+        .resource::<Assets<TiledMap>>()
+        .get(handle)
+        .expect("map should be loaded")
+}
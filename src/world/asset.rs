@@ -2,9 +2,15 @@
 
 use bevy::{
     asset::{io::Reader, AssetLoader, AssetPath, LoadContext},
+    math::bounding::Aabb2d,
     prelude::*,
 };
-use std::{fmt, io::ErrorKind};
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
 
 use crate::{cache::TiledResourceCache, reader::BytesResourceReader, TiledMap};
 
@@ -26,8 +32,60 @@ pub struct TiledWorld {
     ///
     /// Contains both the [TiledMap] handle and its associated [Rect] boundary
     /// as defined by the `.world` file.
+    /// If the `.world` file does not provide a size for a map, it is read from the map itself.
     /// Note that the actual map boundaries are not taken into account for world chunking.
     pub maps: Vec<(Rect, Handle<TiledMap>)>,
+    /// Whether only maps adjacent to the one the camera is in should be spawned.
+    ///
+    /// Read from the `onlyShowAdjacentMaps` field of the `.world` file and only
+    /// relevant when world chunking is enabled.
+    pub only_show_adjacent: bool,
+    /// Order in which maps should be spawned.
+    ///
+    /// Read from the `mapSortOrder` field of the `.world` file.
+    pub sort_order: TiledWorldSortOrder,
+}
+
+/// Order in which the maps of a [TiledWorld] are spawned.
+#[derive(Default, Copy, Clone, PartialEq, Eq, Debug)]
+pub enum TiledWorldSortOrder {
+    /// Maps are spawned in the same order as in the `.world` file.
+    ///
+    /// When using world chunking, maps entering the chunking area during the same frame are
+    /// instead spawned by increasing distance to the closest [Camera](bevy::prelude::Camera).
+    #[default]
+    Manual,
+    /// Maps are spawned sorted by their file name.
+    FileName,
+}
+
+/// `.world` file fields which are not parsed by the `tiled` crate.
+#[derive(Default, PartialEq, Debug)]
+struct WorldFileOptions {
+    only_show_adjacent_maps: bool,
+    map_sort_order: Option<String>,
+}
+
+impl WorldFileOptions {
+    /// Read options from the `.world` file JSON content.
+    ///
+    /// These fields only appear at the top-level of the file and their values are a boolean
+    /// and a plain string, so we can look them up without pulling a full JSON parser.
+    fn parse(json: &str) -> Self {
+        Self {
+            only_show_adjacent_maps: json_value(json, "onlyShowAdjacentMaps")
+                .is_some_and(|value| value.starts_with("true")),
+            map_sort_order: json_value(json, "mapSortOrder")
+                .and_then(|value| value.strip_prefix('"')?.split('"').next())
+                .map(String::from),
+        }
+    }
+}
+
+/// Returns the JSON text starting at the value of the first occurrence of given key.
+fn json_value<'a>(json: &'a str, key: &str) -> Option<&'a str> {
+    let (_, rest) = json.split_once(&format!("\"{key}\""))?;
+    rest.trim_start().strip_prefix(':').map(str::trim_start)
 }
 
 impl TiledWorld {
@@ -42,6 +100,40 @@ impl TiledWorld {
             TiledMapAnchor::BottomLeft => Vec3::ZERO,
         }
     }
+
+    /// Call the given closure for each map of this world, with the map index and its world-space [Aabb2d].
+    ///
+    /// Map boundaries are the ones defined by the `.world` file, transformed using the world
+    /// [GlobalTransform] and the [TiledMapAnchor] offset.
+    pub fn for_each_map<F: FnMut(usize, Aabb2d)>(
+        &self,
+        world_transform: &GlobalTransform,
+        anchor: &TiledMapAnchor,
+        mut f: F,
+    ) {
+        let (_, r, t) = world_transform
+            .mul_transform(Transform::from_translation(self.offset(anchor)))
+            .to_scale_rotation_translation();
+        let (axis, mut angle) = r.to_axis_angle();
+        if axis.z < 0. {
+            angle = -angle;
+        }
+        let world_isometry = Isometry2d::new(Vec2::new(t.x, t.y), Rot2::radians(angle));
+        for (idx, (rect, _)) in self.maps.iter().enumerate() {
+            f(
+                idx,
+                Aabb2d::from_point_cloud(
+                    Isometry2d::IDENTITY,
+                    &[
+                        world_isometry.transform_point(Vec2::new(rect.min.x, rect.min.y)),
+                        world_isometry.transform_point(Vec2::new(rect.min.x, rect.max.y)),
+                        world_isometry.transform_point(Vec2::new(rect.max.x, rect.max.y)),
+                        world_isometry.transform_point(Vec2::new(rect.max.x, rect.min.y)),
+                    ],
+                ),
+            );
+        }
+    }
 }
 
 impl fmt::Debug for TiledWorld {
@@ -49,6 +141,8 @@ impl fmt::Debug for TiledWorld {
         f.debug_struct("TiledWorld")
             .field("world.source", &self.world.source)
             .field("rect", &self.rect)
+            .field("only_show_adjacent", &self.only_show_adjacent)
+            .field("sort_order", &self.sort_order)
             .finish()
     }
 }
@@ -65,6 +159,37 @@ pub enum TiledWorldLoaderError {
     /// Found an infinite map in this world which is not supported
     #[error("Infinite map found in this world (not supported)")]
     WorldWithInfiniteMap,
+    /// A map of this world references a world file, possibly this world itself
+    #[error("World map {path:?} is a world file: circular references are not supported")]
+    CircularReference {
+        /// Path of the offending map
+        path: PathBuf,
+    },
+}
+
+/// [TiledWorldLoader] settings.
+///
+/// Can be provided when loading a world using [AssetServer::load_with_settings].
+#[derive(Default, Serialize, Deserialize, Clone, Debug)]
+pub struct TiledWorldLoaderSettings {
+    /// Prefix prepended to each map path from the `.world` file.
+    ///
+    /// By default, maps paths are relative to the `.world` file.
+    /// If set, maps paths are instead built by prepending this prefix to the
+    /// map file name, which allows loading maps from another asset source,
+    /// for instance `remote://maps/`.
+    pub map_asset_prefix: Option<String>,
+}
+
+impl TiledWorldLoaderSettings {
+    /// Compute the [AssetPath] of a world map, given its file name.
+    fn map_asset_path(&self, world_path: &Path, filename: &str) -> AssetPath<'static> {
+        match &self.map_asset_prefix {
+            Some(prefix) => AssetPath::parse(&format!("{prefix}{filename}")).into_owned(),
+            // Seems safe to unwrap() here since we do it on the world path (which should always have a parent)
+            None => AssetPath::from(world_path.parent().unwrap().join(filename)),
+        }
+    }
 }
 
 pub(crate) struct TiledWorldLoader {
@@ -79,15 +204,44 @@ impl FromWorld for TiledWorldLoader {
     }
 }
 
+/// Read a map size, in pixels, directly from its `.tmx` file.
+async fn read_map_size(
+    map_path: AssetPath<'static>,
+    cache: &TiledResourceCache,
+    load_context: &mut LoadContext<'_>,
+) -> Result<(i32, i32), TiledWorldLoaderError> {
+    let bytes = load_context
+        .read_asset_bytes(map_path.clone())
+        .await
+        .map_err(|e| std::io::Error::new(ErrorKind::NotFound, e))?;
+    let map_path: PathBuf = map_path.path().to_path_buf();
+    let map = {
+        let mut loader = tiled::Loader::with_cache_and_reader(
+            cache.clone(),
+            BytesResourceReader::new(&bytes, load_context),
+        );
+        loader.load_tmx_map(&map_path).map_err(|e| {
+            std::io::Error::new(ErrorKind::Other, format!("Could not load TMX map: {e}"))
+        })?
+    };
+    if map.infinite() {
+        return Err(TiledWorldLoaderError::WorldWithInfiniteMap);
+    }
+    Ok((
+        (map.width * map.tile_width) as i32,
+        (map.height * map.tile_height) as i32,
+    ))
+}
+
 impl AssetLoader for TiledWorldLoader {
     type Asset = TiledWorld;
-    type Settings = ();
+    type Settings = TiledWorldLoaderSettings;
     type Error = TiledWorldLoaderError;
 
     async fn load(
         &self,
         reader: &mut dyn Reader,
-        _settings: &Self::Settings,
+        settings: &Self::Settings,
         load_context: &mut LoadContext<'_>,
     ) -> Result<Self::Asset, Self::Error> {
         let mut bytes = Vec::new();
@@ -111,18 +265,64 @@ impl AssetLoader for TiledWorldLoader {
             return Err(TiledWorldLoaderError::EmptyWorld);
         }
 
-        // Calculate the full rect of the world
-        let mut world_rect = Rect::new(0.0, 0.0, 0.0, 0.0);
+        // File is valid JSON since it was successfully loaded by the tiled crate
+        let options = std::str::from_utf8(&bytes)
+            .map(WorldFileOptions::parse)
+            .unwrap_or_default();
+        let sort_order = match options.map_sort_order.as_deref() {
+            None | Some("manual") => TiledWorldSortOrder::Manual,
+            Some("fileName") => TiledWorldSortOrder::FileName,
+            Some(other) => {
+                warn!("Unknown world map sort order '{other}', maps will be spawned in file order");
+                TiledWorldSortOrder::Manual
+            }
+        };
+
+        // Maps are loaded as TMX files: a world referencing a world file (including itself)
+        // would never load properly, so reject it right away
+        for map in world.maps.iter() {
+            let map_path = settings.map_asset_path(&world_path, &map.filename);
+            if map_path.path() == world_path
+                || map_path
+                    .path()
+                    .extension()
+                    .is_some_and(|ext| ext == "world")
+            {
+                return Err(TiledWorldLoaderError::CircularReference {
+                    path: map_path.path().to_path_buf(),
+                });
+            }
+        }
+
+        // Retrieve each map size: if it's not provided by the .world file,
+        // read it from the map itself
+        let mut map_sizes = Vec::with_capacity(world.maps.len());
         for map in world.maps.iter() {
             let (Some(map_width), Some(map_height)) = (map.width, map.height) else {
                 // Assume that we cannot get map width / map height because it's an infinite map
                 return Err(TiledWorldLoaderError::WorldWithInfiniteMap);
             };
+            if map_width > 0 && map_height > 0 {
+                map_sizes.push((map_width, map_height));
+                continue;
+            }
+
+            let map_path = settings.map_asset_path(&world_path, &map.filename);
+            debug!(
+                "Map '{}' has no size in world file, read it from the map",
+                map_path
+            );
+            map_sizes.push(read_map_size(map_path, &self.cache, load_context).await?);
+        }
+
+        // Calculate the full rect of the world
+        let mut world_rect = Rect::new(0.0, 0.0, 0.0, 0.0);
+        for (map, (map_width, map_height)) in world.maps.iter().zip(map_sizes.iter()) {
             let map_rect = Rect::new(
                 map.x as f32,
                 map.y as f32, // Invert for Tiled to Bevy Y axis
-                map.x as f32 + map_width as f32,
-                map.y as f32 + map_height as f32,
+                map.x as f32 + *map_width as f32,
+                map.y as f32 + *map_height as f32,
             );
 
             world_rect = world_rect.union(map_rect);
@@ -130,23 +330,17 @@ impl AssetLoader for TiledWorldLoader {
 
         // Load all maps
         let mut maps = Vec::new();
-        for map in world.maps.iter() {
-            // Seems safe to unwrap() here since we do it on the world path (which should always have a parent)
-            let map_path = world_path.parent().unwrap().join(map.filename.clone());
-
-            let (Some(map_width), Some(map_height)) = (map.width, map.height) else {
-                // Assume that we cannot get map width / map height because it's an infinite map
-                return Err(TiledWorldLoaderError::WorldWithInfiniteMap);
-            };
+        for (map, (map_width, map_height)) in world.maps.iter().zip(map_sizes.iter()) {
+            let map_path = settings.map_asset_path(&world_path, &map.filename);
 
             maps.push((
                 Rect::new(
                     map.x as f32,
-                    world_rect.max.y - map_height as f32 - map.y as f32, // Invert for Tiled to Bevy Y axis
-                    map.x as f32 + map_width as f32,
+                    world_rect.max.y - *map_height as f32 - map.y as f32, // Invert for Tiled to Bevy Y axis
+                    map.x as f32 + *map_width as f32,
                     world_rect.max.y - map.y as f32,
                 ),
-                load_context.load(AssetPath::from(map_path)),
+                load_context.load(map_path),
             ));
         }
 
@@ -156,6 +350,8 @@ impl AssetLoader for TiledWorldLoader {
             world,
             rect: world_rect,
             maps,
+            only_show_adjacent: options.only_show_adjacent_maps,
+            sort_order,
         };
         debug!(
             "Loaded world '{}': {:?}",
@@ -170,3 +366,31 @@ impl AssetLoader for TiledWorldLoader {
         EXTENSIONS
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_world_file_options() {
+        let options = WorldFileOptions::parse(
+            r#"{
+                "maps": [{"fileName": "a.tmx", "height": 320, "width": 320, "x": 0, "y": 0}],
+                "mapSortOrder" : "fileName",
+                "onlyShowAdjacentMaps": true,
+                "type": "world"
+            }"#,
+        );
+        assert_eq!(
+            options,
+            WorldFileOptions {
+                only_show_adjacent_maps: true,
+                map_sort_order: Some(String::from("fileName")),
+            }
+        );
+        assert_eq!(
+            WorldFileOptions::parse(r#"{"maps": [], "onlyShowAdjacentMaps": false}"#),
+            WorldFileOptions::default()
+        );
+    }
+}
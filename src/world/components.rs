@@ -1,5 +1,6 @@
 //! This module contains all map [Component]s definition.
 
+use crate::prelude::*;
 use bevy::{prelude::*, utils::HashMap};
 
 /// [Component] holding Tiled world chunking configuration.
@@ -11,15 +12,67 @@ use bevy::{prelude::*, utils::HashMap};
 /// Must be added to the [Entity] holding the world.
 #[derive(Component, Default, Reflect, Copy, Clone, Debug)]
 #[reflect(Component, Default, Debug)]
-pub struct TiledWorldChunking(pub Option<Vec2>);
+pub struct TiledWorldChunking(pub Option<ChunkingHalfExtents>);
 
 impl TiledWorldChunking {
-    /// Initialize world chunking with provided size
+    /// Initialize world chunking with provided half extents
     pub fn new(width: f32, height: f32) -> Self {
-        Self(Some(Vec2::new(width, height)))
+        Self(Some(ChunkingHalfExtents(Vec2::new(width, height))))
     }
 }
 
+/// Half extents (in pixel) of the area around each [Camera] used for world chunking.
+///
+/// The area spans from `camera_position - half_extents` to `camera_position + half_extents`.
+#[derive(Default, Reflect, Copy, Clone, PartialEq, Debug)]
+#[reflect(Default, Debug, PartialEq)]
+pub struct ChunkingHalfExtents(pub Vec2);
+
+impl ChunkingHalfExtents {
+    /// Create half extents from the full size of the area, for instance the screen size.
+    pub fn from_screen_size(size: Vec2) -> Self {
+        Self(size / 2.)
+    }
+}
+
+/// Margins (in pixel) added around each [Camera] area when using [TiledWorldChunking].
+///
+/// Can be added to the [Entity] holding the world:
+/// - `preload` expands the area used to spawn maps, so they are spawned slightly before
+///   they enter the view.
+/// - `despawn` further expands this area when checking which maps should be despawned.
+///   Maps are only despawned once they are farther than this margin, which avoids spawning
+///   and despawning them over and over when a [Camera] stands on a map border.
+#[derive(Component, Default, Reflect, Copy, Clone, Debug)]
+#[reflect(Component, Default, Debug)]
+pub struct TiledWorldChunkingMargins {
+    /// Margin added to chunking half extents to determine which maps should be spawned.
+    pub preload: Option<Vec2>,
+    /// Margin added to the spawn area to determine which maps should be despawned.
+    pub despawn: Option<Vec2>,
+}
+
+impl TiledWorldChunkingMargins {
+    /// Half extents of the area in which maps should be spawned.
+    pub fn spawn_half_extents(&self, half_extents: Vec2) -> Vec2 {
+        half_extents + self.preload.unwrap_or_default()
+    }
+
+    /// Half extents of the area out of which maps should be despawned.
+    pub fn despawn_half_extents(&self, half_extents: Vec2) -> Vec2 {
+        self.spawn_half_extents(half_extents) + self.despawn.unwrap_or_default()
+    }
+}
+
+/// Marker [Component] for the [Camera]s used for world chunking.
+///
+/// By default, all [Camera]s are used to determine which maps should be spawned when using
+/// [TiledWorldChunking]. If at least one [Camera] has this marker, only the [Camera]s with this
+/// marker are used, for instance to ignore an editor or minimap [Camera].
+#[derive(Component, Default, Reflect, Copy, Clone, Debug)]
+#[reflect(Component, Default, Debug)]
+pub struct TiledWorldChunkingCamera;
+
 /// Marker [Component] for a Tiled world.
 #[derive(Component, Default, Reflect, Copy, Clone, Debug)]
 #[reflect(Component, Default, Debug)]
@@ -27,11 +80,37 @@ pub struct TiledWorldMarker;
 
 /// Marker [Component] to trigger a world respawn.
 ///
-/// Must be added to the [Entity] holding the map.
+/// Must be added to the [Entity] holding the world.
+/// The equivalent [Component] for maps is [RespawnTiledMap](crate::prelude::RespawnTiledMap).
 #[derive(Component, Default, Reflect, Copy, Clone, Debug)]
 #[reflect(Component, Default, Debug)]
 pub struct RespawnTiledWorld;
 
+/// [Component] reporting a Tiled world loading progress.
+///
+/// Inserted on the world [Entity] while the world is loading and removed once it has been fully loaded.
+/// Can be used to display a loading bar.
+#[derive(Component, Default, Reflect, Copy, Clone, Debug)]
+#[reflect(Component, Default, Debug)]
+pub struct TiledWorldProgress {
+    /// Total number of maps in this world
+    ///
+    /// Will be 0 until the `.world` file itself has been loaded.
+    pub total_maps: usize,
+    /// Number of maps which are fully loaded, including their dependencies
+    pub loaded_maps: usize,
+}
+
+impl TiledWorldProgress {
+    /// Loading progress, between 0 and 1.
+    pub fn ratio(&self) -> f32 {
+        if self.total_maps == 0 {
+            return 0.;
+        }
+        self.loaded_maps as f32 / self.total_maps as f32
+    }
+}
+
 /// [Component] storing all the Tiled maps that are composing this world.
 /// Makes the association between Tiled ID and corresponding Bevy [Entity].
 ///
@@ -39,7 +118,7 @@ pub struct RespawnTiledWorld;
 #[derive(Component, Default, Reflect, Clone, Debug)]
 #[reflect(Component, Default, Debug)]
 pub struct TiledWorldStorage {
-    /// Map of maps entities, using the map index from [super::asset::TiledWorld]
-    /// maps list as key.
-    pub spawned_maps: HashMap<usize, Entity>,
+    /// Map of maps entities and their (weak) [Handle], using the map index from
+    /// [super::asset::TiledWorld] maps list as key.
+    pub spawned_maps: HashMap<usize, (Entity, Handle<TiledMap>)>,
 }
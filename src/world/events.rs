@@ -1,11 +1,15 @@
 //! Events related to Tiled world loading
 //!
-//! These events will be fired after the whole map has loaded.
+//! These events will be fired after the whole world has loaded or when world chunking
+//! spawns or despawns one of its maps.
 
 use crate::prelude::*;
 use bevy::prelude::*;
 
 /// Event sent when a Tiled world has finished loading.
+///
+/// It is only sent once per world: a [TiledMapSpawned] event is sent for each map
+/// spawned by this world.
 #[derive(Component, Reflect, Copy, Clone, Debug)]
 #[reflect(Component, Debug)]
 pub struct TiledWorldCreated {
@@ -29,3 +33,45 @@ impl<'a> TiledWorldCreated {
         world_asset.get(self.asset_id)
     }
 }
+
+/// Event sent when a map from a Tiled world is spawned.
+///
+/// This event is sent each time a map is spawned by the world, for instance because of world chunking.
+/// Note that it is sent when the map [Entity] is spawned, not when the map has finished loading:
+/// you should rely on [TiledMapCreated] for that.
+#[derive(Component, Reflect, Copy, Clone, Debug)]
+#[reflect(Component, Debug)]
+pub struct TiledMapSpawned {
+    /// [Entity] of the world this map belongs to.
+    pub world_entity: Entity,
+    /// Spawned map [Entity].
+    pub map_entity: Entity,
+    /// Index of the map in the [TiledWorld] maps list.
+    pub map_index: usize,
+}
+
+impl Event for TiledMapSpawned {
+    type Traversal = &'static Parent;
+    const AUTO_PROPAGATE: bool = true;
+}
+
+/// Event sent when a map from a Tiled world is despawned.
+///
+/// This event is sent each time a map is despawned by the world, for instance because of world chunking.
+/// The map [Entity] despawn command is issued at the same time this event is sent.
+/// If the map was spawned, a [TiledMapUnloaded] event is also sent for it.
+#[derive(Component, Reflect, Copy, Clone, Debug)]
+#[reflect(Component, Debug)]
+pub struct TiledMapDespawned {
+    /// [Entity] of the world this map belonged to.
+    pub world_entity: Entity,
+    /// Despawned map [Entity].
+    pub map_entity: Entity,
+    /// Index of the map in the [TiledWorld] maps list.
+    pub map_index: usize,
+}
+
+impl Event for TiledMapDespawned {
+    type Traversal = &'static Parent;
+    const AUTO_PROPAGATE: bool = true;
+}
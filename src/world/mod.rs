@@ -9,20 +9,34 @@ pub mod prelude {
     pub use super::asset::*;
     pub use super::components::*;
     pub use super::events::*;
-    pub use super::TiledWorldHandle;
+    pub use super::{tiled_world_ready, TiledWorldHandle};
 }
 
 use crate::prelude::*;
 use bevy::{
-    asset::RecursiveDependencyLoadState,
-    math::bounding::{Aabb2d, IntersectsVolume},
+    asset::{AssetPath, RecursiveDependencyLoadState},
+    math::{
+        bounding::{Aabb2d, BoundingVolume, IntersectsVolume},
+        FloatOrd,
+    },
     prelude::*,
+    utils::HashMap,
 };
 use bevy_ecs_tilemap::map::TilemapRenderSettings;
 
+use crate::map::{TiledMapSettings, TiledMapSettingsChanged};
+
 /// Wrapper around the [Handle] to the `.world` file representing the [TiledWorld].
 ///
 /// This is the main [Component] that must be spawned to load a Tiled world.
+///
+/// Map settings components ([TiledMapAnchor], [TiledMapLayerZOffset], [TilemapRenderSettings])
+/// are required with their default value, so spawning this [Component] alone is enough.
+/// They only need to be inserted on the world [Entity] to customize them: they are then
+/// forwarded to all the maps spawned by this world, along with optional [TiledMapLayerZFn],
+/// [TiledMapLayerVisibilityFn], [TiledMapTileSizeOverride], [TiledMapTypeOverride],
+/// [TiledMapPostSpawnHook], [TiledMapLayerFilter], [TiledMapSpawnRowsPerFrame],
+/// [TiledMapSkipTileEntities] and [TiledMapYFlip] components.
 #[derive(Component, Reflect, Clone, Debug)]
 #[reflect(Component, Debug)]
 #[require(
@@ -36,26 +50,82 @@ use bevy_ecs_tilemap::map::TilemapRenderSettings;
 )]
 pub struct TiledWorldHandle(pub Handle<TiledWorld>);
 
+impl TiledWorldHandle {
+    /// Load the `.world` file at given path and wrap the resulting [Handle].
+    ///
+    /// Example:
+    /// ```rust,no_run
+    /// use bevy::prelude::*;
+    /// use bevy_ecs_tiled::prelude::*;
+    ///
+    /// fn startup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    ///     commands.spawn(TiledWorldHandle::from_path("demo.world", &asset_server));
+    /// }
+    /// ```
+    pub fn from_path(path: impl Into<AssetPath<'static>>, asset_server: &AssetServer) -> Self {
+        Self(asset_server.load(path))
+    }
+}
+
+/// Run condition returning `true` once at least one world has been spawned.
+///
+/// Example:
+/// ```rust,no_run
+/// use bevy::prelude::*;
+/// use bevy_ecs_tiled::prelude::*;
+///
+/// fn my_system() {}
+///
+/// App::new()
+///     .add_systems(Update, my_system.run_if(tiled_world_ready));
+/// ```
+pub fn tiled_world_ready(query: Query<(), With<TiledWorldMarker>>) -> bool {
+    !query.is_empty()
+}
+
 pub(crate) fn build(app: &mut bevy::prelude::App) {
     app.init_asset::<TiledWorld>()
         .init_asset_loader::<TiledWorldLoader>()
         .register_type::<TiledWorldHandle>()
         .register_type::<TiledWorldChunking>()
+        .register_type::<ChunkingHalfExtents>()
+        .register_type::<TiledWorldChunkingMargins>()
+        .register_type::<TiledWorldChunkingCamera>()
         .register_type::<TiledWorldMarker>()
         .register_type::<RespawnTiledWorld>()
+        .register_type::<TiledWorldProgress>()
         .register_type::<TiledWorldStorage>()
         .add_event::<TiledWorldCreated>()
         .register_type::<TiledWorldCreated>()
+        .add_event::<TiledMapSpawned>()
+        .register_type::<TiledMapSpawned>()
+        .add_event::<TiledMapDespawned>()
+        .register_type::<TiledMapDespawned>()
         .add_systems(
             PreUpdate,
             process_loaded_worlds.after(crate::map::process_loaded_maps),
         )
-        .add_systems(PostUpdate, (handle_world_events, world_chunking).chain());
+        .add_systems(
+            PostUpdate,
+            (handle_world_events, world_chunking)
+                .chain()
+                .after(TransformSystem::TransformPropagate),
+        );
 }
 
-#[allow(clippy::type_complexity)]
+/// System to spawn and despawn world maps according to cameras positions.
+///
+/// A world is only processed when something chunking depends on has changed: a camera moved,
+/// was added or removed, the world itself was moved, (re)spawned or its chunking settings changed.
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
 fn world_chunking(
-    camera_query: Query<&Transform, (With<Camera>, Changed<Transform>)>,
+    camera_query: Query<
+        (Ref<GlobalTransform>, Option<Ref<TiledWorldChunkingCamera>>),
+        With<Camera>,
+    >,
+    mut removed_cameras: RemovedComponents<Camera>,
+    mut removed_camera_markers: RemovedComponents<TiledWorldChunkingCamera>,
+    mut removed_margins: RemovedComponents<TiledWorldChunkingMargins>,
     worlds: Res<Assets<TiledWorld>>,
     asset_server: Res<AssetServer>,
     mut commands: Commands,
@@ -63,27 +133,48 @@ fn world_chunking(
         (
             Entity,
             &TiledWorldHandle,
-            &GlobalTransform,
-            &TiledWorldChunking,
-            &TiledMapAnchor,
-            &TiledMapLayerZOffset,
-            &TilemapRenderSettings,
+            Ref<GlobalTransform>,
+            Ref<TiledWorldChunking>,
+            Option<Ref<TiledWorldChunkingMargins>>,
+            Ref<TiledWorldMarker>,
+            TiledMapSettings,
             &mut TiledWorldStorage,
         ),
         With<TiledWorldMarker>,
     >,
+    mut map_spawned_event: EventWriter<TiledMapSpawned>,
+    mut map_despawned_event: EventWriter<TiledMapDespawned>,
 ) {
+    // Drain both readers so removals are not reported again next frame
+    let cameras_removed =
+        removed_cameras.read().count() + removed_camera_markers.read().count() > 0;
+    let cameras_changed = cameras_removed
+        || camera_query.iter().any(|(transform, marker)| {
+            transform.is_changed() || marker.is_some_and(|m| m.is_added())
+        });
+    let removed_margins = removed_margins.read().collect::<Vec<_>>();
+
     for (
         world_entity,
         world_handle,
         world_transform,
         world_chunking,
-        anchor,
-        layer_offset,
-        render_settings,
+        chunking_margins,
+        world_marker,
+        settings,
         mut storage,
     ) in world_query.iter_mut()
     {
+        let world_changed = world_transform.is_changed()
+            || world_chunking.is_changed()
+            || chunking_margins.as_ref().is_some_and(|m| m.is_changed())
+            || removed_margins.contains(&world_entity)
+            || world_marker.is_changed()
+            || storage.is_changed();
+        if !cameras_changed && !world_changed {
+            continue;
+        }
+
         // Make sure we have a valid reference on a fully loaded world asset
         let Some(tiled_world) = asset_server
             .get_recursive_dependency_load_state(&world_handle.0)
@@ -101,28 +192,63 @@ fn world_chunking(
         let mut to_spawn = Vec::new();
 
         // Compute static offset based upon world settings
-        let offset = tiled_world.offset(anchor);
+        let offset = tiled_world.offset(settings.anchor);
 
-        if let Some(chunking) = world_chunking.0 {
-            let mut visible_maps = Vec::new();
-            let cameras: Vec<Aabb2d> = camera_query
+        if let Some(ChunkingHalfExtents(chunking)) = world_chunking.0 {
+            let margins = chunking_margins.as_deref().copied().unwrap_or_default();
+            // Only use cameras with the TiledWorldChunkingCamera marker, if any
+            let use_marked_cameras = camera_query.iter().any(|(_, marker)| marker.is_some());
+            let cameras: Vec<Vec2> = camera_query
+                .iter()
+                .filter(|(_, marker)| !use_marked_cameras || marker.is_some())
+                .map(|(transform, _)| transform.translation().truncate())
+                .collect();
+            // Maps are spawned when they enter the spawn area but are only despawned
+            // once they leave the (larger) despawn area
+            let spawn_areas: Vec<Aabb2d> = cameras
+                .iter()
+                .map(|c| Aabb2d::new(*c, margins.spawn_half_extents(chunking)))
+                .collect();
+            let despawn_areas: Vec<Aabb2d> = cameras
                 .iter()
-                .map(|transform| {
-                    Aabb2d::new(
-                        Vec2::new(transform.translation.x, transform.translation.y),
-                        chunking,
-                    )
-                })
+                .map(|c| Aabb2d::new(*c, margins.despawn_half_extents(chunking)))
                 .collect();
+            let mut maps_aabb = HashMap::new();
+            tiled_world.for_each_map(&world_transform, settings.anchor, |idx, aabb| {
+                maps_aabb.insert(idx, aabb);
+            });
+            // Maps the cameras are in, only needed to restrict spawned maps to adjacent ones
+            let current_maps: Vec<(usize, Aabb2d)> = if tiled_world.only_show_adjacent {
+                maps_aabb
+                    .iter()
+                    .filter(|(_, aabb)| cameras.iter().any(|c| aabb.closest_point(*c) == *c))
+                    .map(|(idx, aabb)| (*idx, *aabb))
+                    .collect()
+            } else {
+                Vec::new()
+            };
             // Check which map is visible by testing them against each camera (if there are multiple)
-            // If map aabb overlaps with the camera_view, it is visible
-            for_each_map(tiled_world, world_transform, offset, |idx, aabb| {
-                for c in cameras.iter() {
-                    if aabb.intersects(c) {
-                        visible_maps.push(idx);
-                    }
+            // If map aabb overlaps with the camera area, it is visible
+            let mut visible_maps = Vec::new();
+            let mut kept_maps = Vec::new();
+            for idx in 0..tiled_world.maps.len() {
+                let Some(aabb) = maps_aabb.get(&idx) else {
+                    continue;
+                };
+                if tiled_world.only_show_adjacent
+                    && !current_maps.iter().any(|(current_idx, current)| {
+                        *current_idx == idx || are_adjacent(current, aabb)
+                    })
+                {
+                    continue;
                 }
-            });
+                if spawn_areas.iter().any(|area| aabb.intersects(area)) {
+                    visible_maps.push(idx);
+                }
+                if despawn_areas.iter().any(|area| aabb.intersects(area)) {
+                    kept_maps.push(idx);
+                }
+            }
 
             // All the maps that are visible but not already spawned should be spawned
             for idx in visible_maps.iter() {
@@ -131,48 +257,79 @@ fn world_chunking(
                 }
             }
 
-            // All the maps that are spawned but not visible should be removed
+            // Spawn maps closest to a camera first, to reduce pop-in when many maps
+            // need to be spawned at once (note it can be overridden by the world sort order)
+            to_spawn.sort_by_cached_key(|idx| {
+                FloatOrd(maps_aabb.get(idx).map_or(f32::MAX, |aabb| {
+                    cameras
+                        .iter()
+                        .map(|c| c.distance_squared(aabb.center()))
+                        .fold(f32::MAX, f32::min)
+                }))
+            });
+
+            // All the maps that are spawned but out of the despawn area should be removed
             for (idx, _) in storage.spawned_maps.iter() {
-                if !visible_maps.iter().any(|i| i == idx) {
+                if !kept_maps.iter().any(|i| i == idx) {
                     to_remove.push(*idx);
                 }
             }
-        } else if storage.spawned_maps.is_empty() {
-            // No chunking and we don't have spawned any map yet: just spawn all maps
-            for idx in 0..tiled_world.maps.len() - 1 {
-                to_spawn.push(idx);
+        } else {
+            // No chunking: just spawn all maps which are not spawned yet
+            for idx in 0..tiled_world.maps.len() {
+                if !storage.spawned_maps.contains_key(&idx) {
+                    to_spawn.push(idx);
+                }
             }
         }
 
         // Despawn maps
         for idx in to_remove {
-            if let Some(map_entity) = storage.spawned_maps.remove(&idx) {
+            if let Some((map_entity, _)) = storage.spawned_maps.remove(&idx) {
                 debug!("Despawn map (index = {}, entity = {:?})", idx, map_entity);
-                commands.entity(map_entity).despawn_recursive();
+                despawn_map(
+                    &mut commands,
+                    world_entity,
+                    map_entity,
+                    idx,
+                    &mut map_despawned_event,
+                );
             }
         }
 
+        if tiled_world.sort_order == TiledWorldSortOrder::FileName {
+            to_spawn.sort_by_key(|idx| tiled_world.world.maps.get(*idx).map(|m| &m.filename));
+        }
+
         // Spawn maps
         for idx in to_spawn {
-            let Some((rect, handle)) = tiled_world.maps.get(idx) else {
+            let (Some((rect, handle)), Some(world_map)) =
+                (tiled_world.maps.get(idx), tiled_world.world.maps.get(idx))
+            else {
                 continue;
             };
-            let map_entity = commands
-                .spawn((
-                    TiledMapHandle(handle.clone_weak()),
-                    Transform::from_translation(offset + Vec3::new(rect.min.x, rect.min.y, 0.0)),
-                    // Force map anchor to BottomLeft: everything is handled at world level
-                    TiledMapAnchor::BottomLeft,
-                    *layer_offset,
-                    *render_settings,
-                ))
-                .set_parent(world_entity)
-                .id();
+            let mut map_entity = commands.spawn((
+                TiledMapHandle(handle.clone_weak()),
+                TiledMapId(world_map.filename.clone()),
+                Transform::from_translation(offset + Vec3::new(rect.min.x, rect.min.y, 0.0)),
+            ));
+            settings.insert_into(&mut map_entity);
+            let map_entity = map_entity.set_parent(world_entity).id();
             debug!(
                 "Spawn map (index = {}, handle = {:?},  entity = {:?})",
                 idx, handle, map_entity
             );
-            storage.spawned_maps.insert(idx, map_entity);
+            storage
+                .spawned_maps
+                .insert(idx, (map_entity, handle.clone_weak()));
+
+            let event = TiledMapSpawned {
+                world_entity,
+                map_entity,
+                map_index: idx,
+            };
+            commands.trigger_targets(event, world_entity);
+            map_spawned_event.send(event);
         }
     }
 }
@@ -184,21 +341,28 @@ fn process_loaded_worlds(
     mut commands: Commands,
     worlds: Res<Assets<TiledWorld>>,
     mut world_query: Query<
-        (Entity, &TiledWorldHandle, &mut TiledWorldStorage),
+        (
+            Entity,
+            &TiledWorldHandle,
+            &TiledMapAnchor,
+            Has<ReloadTiledWorld>,
+            &mut TiledWorldStorage,
+        ),
         Or<(
             Changed<TiledWorldHandle>,
             // If a world settings change, force a respawn so they can be taken into account
-            Changed<TiledMapAnchor>,
-            Changed<TiledMapLayerZOffset>,
-            Changed<TilemapRenderSettings>,
+            TiledMapSettingsChanged,
             With<RespawnTiledWorld>,
             // Not needed to react to changes on TiledWorldChunking:
             // it's read each frame by world_chunking() system
         )>,
     >,
+    mut map_query: Query<&mut Transform, With<TiledMapHandle>>,
     mut world_event: EventWriter<TiledWorldCreated>,
+    mut map_despawned_event: EventWriter<TiledMapDespawned>,
 ) {
-    for (world_entity, world_handle, mut world_storage) in world_query.iter_mut() {
+    for (world_entity, world_handle, anchor, is_reload, mut world_storage) in world_query.iter_mut()
+    {
         if let Some(load_state) = asset_server.get_recursive_dependency_load_state(&world_handle.0)
         {
             if !load_state.is_loaded() {
@@ -214,7 +378,22 @@ fn process_loaded_worlds(
                         "World is not fully loaded yet, will try again next frame (handle = {:?} / entity = {:?})",
                         world_handle.0, world_entity
                     );
-                    commands.entity(world_entity).insert(RespawnTiledWorld);
+                    let progress = worlds
+                        .get(&world_handle.0)
+                        .map(|tiled_world| TiledWorldProgress {
+                            total_maps: tiled_world.maps.len(),
+                            loaded_maps: tiled_world
+                                .maps
+                                .iter()
+                                .filter(|(_, handle)| {
+                                    asset_server.is_loaded_with_dependencies(handle)
+                                })
+                                .count(),
+                        })
+                        .unwrap_or_default();
+                    commands
+                        .entity(world_entity)
+                        .insert((RespawnTiledWorld, progress));
                 }
                 continue;
             }
@@ -226,13 +405,45 @@ fn process_loaded_worlds(
                 continue;
             };
 
+            // Should not happen since the loader rejects worlds without maps, but better be safe
+            if tiled_world.maps.is_empty() {
+                warn!(
+                    "World has no maps, nothing to spawn (handle = {:?} / entity = {:?})",
+                    world_handle.0, world_entity
+                );
+                commands.entity(world_entity).remove::<(
+                    RespawnTiledWorld,
+                    ReloadTiledWorld,
+                    TiledWorldProgress,
+                )>();
+                continue;
+            }
+
             debug!(
                 "World has finished loading, spawn world maps (handle = {:?})",
                 world_handle.0
             );
 
-            // Clean previous maps before trying to spawn the new ones
-            remove_maps(&mut commands, &mut world_storage);
+            if is_reload {
+                // World asset was modified: keep maps which are still valid
+                update_maps(
+                    &mut commands,
+                    world_entity,
+                    tiled_world,
+                    anchor,
+                    &mut world_storage,
+                    &mut map_query,
+                    &mut map_despawned_event,
+                );
+            } else {
+                // Clean previous maps before trying to spawn the new ones
+                remove_maps(
+                    &mut commands,
+                    world_entity,
+                    &mut world_storage,
+                    &mut map_despawned_event,
+                );
+            }
 
             // Remove the 'Respawn' marker and insert additional components
             commands
@@ -244,7 +455,7 @@ fn process_loaded_worlds(
                     )),
                     TiledWorldMarker,
                 ))
-                .remove::<RespawnTiledWorld>();
+                .remove::<(RespawnTiledWorld, ReloadTiledWorld, TiledWorldProgress)>();
 
             let event = TiledWorldCreated {
                 entity: world_entity,
@@ -260,22 +471,31 @@ fn process_loaded_worlds(
 fn handle_world_events(
     mut commands: Commands,
     mut world_events: EventReader<AssetEvent<TiledWorld>>,
-    world_query: Query<(Entity, &TiledWorldHandle)>,
+    mut world_query: Query<(Entity, &TiledWorldHandle, &mut TiledWorldStorage)>,
+    mut map_despawned_event: EventWriter<TiledMapDespawned>,
 ) {
     for event in world_events.read() {
         match event {
             AssetEvent::Modified { id } => {
                 info!("World changed: {id}");
-                for (world_entity, world_handle) in world_query.iter() {
+                for (world_entity, world_handle, _) in world_query.iter() {
                     if world_handle.0.id() == *id {
-                        commands.entity(world_entity).insert(RespawnTiledWorld);
+                        commands
+                            .entity(world_entity)
+                            .insert((RespawnTiledWorld, ReloadTiledWorld));
                     }
                 }
             }
             AssetEvent::Removed { id } => {
                 info!("World removed: {id}");
-                for (world_entity, world_handle) in world_query.iter() {
+                for (world_entity, world_handle, mut world_storage) in world_query.iter_mut() {
                     if world_handle.0.id() == *id {
+                        remove_maps(
+                            &mut commands,
+                            world_entity,
+                            &mut world_storage,
+                            &mut map_despawned_event,
+                        );
                         commands.entity(world_entity).despawn_recursive();
                     }
                 }
@@ -285,39 +505,335 @@ fn handle_world_events(
     }
 }
 
-fn remove_maps(commands: &mut Commands, world_storage: &mut TiledWorldStorage) {
-    for (_, map_entity) in world_storage.spawned_maps.iter() {
-        commands.entity(*map_entity).despawn_recursive();
+fn remove_maps(
+    commands: &mut Commands,
+    world_entity: Entity,
+    world_storage: &mut TiledWorldStorage,
+    map_despawned_event: &mut EventWriter<TiledMapDespawned>,
+) {
+    for (idx, (map_entity, _)) in world_storage.spawned_maps.iter() {
+        despawn_map(
+            commands,
+            world_entity,
+            *map_entity,
+            *idx,
+            map_despawned_event,
+        );
     }
     world_storage.spawned_maps.clear();
 }
 
-pub(crate) fn for_each_map<F: FnMut(usize, Aabb2d)>(
+/// Update spawned maps after the world asset was modified.
+///
+/// Spawned maps are matched against the new world maps list using their handle rather than
+/// their index, since adding or removing a map from the `.world` file shifts indices of all
+/// following maps.
+/// Maps which no longer exist are despawned, other maps entities are kept, re-indexed and
+/// moved to their new position, if needed.
+/// Missing maps will then be spawned by [world_chunking()].
+#[allow(clippy::too_many_arguments)]
+fn update_maps(
+    commands: &mut Commands,
+    world_entity: Entity,
     tiled_world: &TiledWorld,
-    world_transform: &GlobalTransform,
-    offset: Vec3,
-    mut f: F,
+    anchor: &TiledMapAnchor,
+    world_storage: &mut TiledWorldStorage,
+    map_query: &mut Query<&mut Transform, With<TiledMapHandle>>,
+    map_despawned_event: &mut EventWriter<TiledMapDespawned>,
 ) {
-    let (_, r, t) = world_transform
-        .mul_transform(Transform::from_translation(offset))
-        .to_scale_rotation_translation();
-    let (axis, mut angle) = r.to_axis_angle();
-    if axis.z < 0. {
-        angle = -angle;
+    let offset = tiled_world.offset(anchor);
+    let mut spawned_maps = std::mem::take(&mut world_storage.spawned_maps);
+    let matches = match_spawned_maps(
+        spawned_maps
+            .iter()
+            .map(|(idx, (_, handle))| (*idx, handle.id())),
+        &tiled_world
+            .maps
+            .iter()
+            .map(|(_, handle)| handle.id())
+            .collect::<Vec<_>>(),
+    );
+    for (old_idx, new_idx) in matches {
+        let Some((map_entity, handle)) = spawned_maps.remove(&old_idx) else {
+            continue;
+        };
+        match new_idx.and_then(|idx| tiled_world.maps.get(idx).map(|(rect, _)| (idx, rect))) {
+            Some((new_idx, rect)) => {
+                if let Ok(mut transform) = map_query.get_mut(map_entity) {
+                    let translation = offset + Vec3::new(rect.min.x, rect.min.y, 0.0);
+                    if transform.translation != translation {
+                        transform.translation = translation;
+                    }
+                }
+                world_storage
+                    .spawned_maps
+                    .insert(new_idx, (map_entity, handle));
+            }
+            None => {
+                debug!(
+                    "Despawn map (index = {}, entity = {:?})",
+                    old_idx, map_entity
+                );
+                despawn_map(
+                    commands,
+                    world_entity,
+                    map_entity,
+                    old_idx,
+                    map_despawned_event,
+                );
+            }
+        }
     }
-    let world_isometry = Isometry2d::new(Vec2::new(t.x, t.y), Rot2::radians(angle));
-    for (idx, (rect, _)) in tiled_world.maps.iter().enumerate() {
-        f(
-            idx,
-            Aabb2d::from_point_cloud(
-                Isometry2d::IDENTITY,
-                &[
-                    world_isometry.transform_point(Vec2::new(rect.min.x, rect.min.y)),
-                    world_isometry.transform_point(Vec2::new(rect.min.x, rect.max.y)),
-                    world_isometry.transform_point(Vec2::new(rect.max.x, rect.max.y)),
-                    world_isometry.transform_point(Vec2::new(rect.max.x, rect.min.y)),
-                ],
+}
+
+/// Associate each spawned map index with its index in the new world maps list, if any.
+///
+/// A map keeping both its index and its handle is always matched first, so that a map file
+/// referenced several times by the same world does not swap entities needlessly. Other maps
+/// are then matched, in index order, with the first remaining map using the same handle.
+fn match_spawned_maps(
+    spawned: impl Iterator<Item = (usize, AssetId<TiledMap>)>,
+    maps: &[AssetId<TiledMap>],
+) -> Vec<(usize, Option<usize>)> {
+    let mut available = maps.iter().copied().map(Some).collect::<Vec<_>>();
+    let mut spawned = spawned.collect::<Vec<_>>();
+    spawned.sort_by_key(|(idx, _)| *idx);
+
+    let mut matches = Vec::with_capacity(spawned.len());
+    spawned.retain(|(idx, id)| {
+        if available.get(*idx).copied().flatten() == Some(*id) {
+            available[*idx] = None;
+            matches.push((*idx, Some(*idx)));
+            false
+        } else {
+            true
+        }
+    });
+    for (idx, id) in spawned {
+        let new_idx = available.iter().position(|a| *a == Some(id));
+        if let Some(new_idx) = new_idx {
+            available[new_idx] = None;
+        }
+        matches.push((idx, new_idx));
+    }
+    matches
+}
+
+/// Two maps are adjacent if they share (part of) an edge.
+fn are_adjacent(a: &Aabb2d, b: &Aabb2d) -> bool {
+    const EPSILON: f32 = 0.01;
+    let touch_x = (a.max.x - b.min.x).abs() < EPSILON || (b.max.x - a.min.x).abs() < EPSILON;
+    let touch_y = (a.max.y - b.min.y).abs() < EPSILON || (b.max.y - a.min.y).abs() < EPSILON;
+    let overlap_x = a.min.x.max(b.min.x) + EPSILON < a.max.x.min(b.max.x);
+    let overlap_y = a.min.y.max(b.min.y) + EPSILON < a.max.y.min(b.max.y);
+    (touch_x && overlap_y) || (touch_y && overlap_x)
+}
+
+/// Marker [Component] to only update spawned maps instead of respawning them when
+/// a world is respawned because its asset was modified.
+#[derive(Component, Default, Copy, Clone, Debug)]
+pub(crate) struct ReloadTiledWorld;
+
+fn despawn_map(
+    commands: &mut Commands,
+    world_entity: Entity,
+    map_entity: Entity,
+    map_index: usize,
+    map_despawned_event: &mut EventWriter<TiledMapDespawned>,
+) {
+    let event = TiledMapDespawned {
+        world_entity,
+        map_entity,
+        map_index,
+    };
+    commands.trigger_targets(event, world_entity);
+    map_despawned_event.send(event);
+    crate::map::send_map_unloaded(commands, map_entity);
+    commands.entity(map_entity).despawn_recursive();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::*;
+
+    fn aabb(min: (f32, f32), max: (f32, f32)) -> Aabb2d {
+        Aabb2d {
+            min: Vec2::new(min.0, min.1),
+            max: Vec2::new(max.0, max.1),
+        }
+    }
+
+    #[test]
+    fn spawned_maps_are_matched_by_handle() {
+        let a = Handle::<TiledMap>::weak_from_u128(1).id();
+        let b = Handle::<TiledMap>::weak_from_u128(2).id();
+        let c = Handle::<TiledMap>::weak_from_u128(3).id();
+        let sorted = |mut v: Vec<(usize, Option<usize>)>| {
+            v.sort();
+            v
+        };
+
+        // Inserting a map at the beginning of the list shifts other maps
+        let m = match_spawned_maps([(0, a), (1, b)].into_iter(), &[c, a, b]);
+        assert_eq!(sorted(m), vec![(0, Some(1)), (1, Some(2))]);
+
+        // Removing a map only despawns this one
+        let m = match_spawned_maps([(0, a), (1, b), (2, c)].into_iter(), &[a, c]);
+        assert_eq!(sorted(m), vec![(0, Some(0)), (1, None), (2, Some(1))]);
+
+        // A map file used twice keeps its entities in place
+        let m = match_spawned_maps([(0, a), (2, a)].into_iter(), &[a, b, a]);
+        assert_eq!(sorted(m), vec![(0, Some(0)), (2, Some(2))]);
+        let m = match_spawned_maps([(1, a), (2, a)].into_iter(), &[b, a, a, a]);
+        assert_eq!(sorted(m), vec![(1, Some(1)), (2, Some(2))]);
+    }
+
+    #[test]
+    fn maps_sharing_an_edge_are_adjacent() {
+        let map = aabb((0., 0.), (100., 100.));
+        // Right, left, top and bottom neighbours, including partially shared edges
+        assert!(are_adjacent(&map, &aabb((100., 0.), (200., 100.))));
+        assert!(are_adjacent(&map, &aabb((-100., 50.), (0., 150.))));
+        assert!(are_adjacent(&map, &aabb((20., 100.), (60., 200.))));
+        assert!(are_adjacent(&map, &aabb((-50., -100.), (50., 0.))));
+        // Adjacency is symmetric
+        assert!(are_adjacent(&aabb((100., 0.), (200., 100.)), &map));
+    }
+
+    #[test]
+    fn maps_touching_by_a_corner_are_not_adjacent() {
+        let map = aabb((0., 0.), (100., 100.));
+        assert!(!are_adjacent(&map, &aabb((100., 100.), (200., 200.))));
+        assert!(!are_adjacent(&map, &aabb((-100., -100.), (0., 0.))));
+        assert!(!are_adjacent(&map, &aabb((100., -100.), (200., 0.))));
+    }
+
+    #[test]
+    fn distant_or_overlapping_maps_are_not_adjacent() {
+        let map = aabb((0., 0.), (100., 100.));
+        assert!(!are_adjacent(&map, &aabb((150., 0.), (250., 100.))));
+        assert!(!are_adjacent(&map, &aabb((50., 50.), (150., 150.))));
+    }
+
+    #[test]
+    fn chunking_spawns_maps_closest_to_camera_first() {
+        let mut app = test_app();
+        // Camera above the bottom-right map of the 5x5 world, which is the last one in the file
+        let camera = Vec2::new(2300., 250.);
+        app.world_mut().spawn((
+            Camera::default(),
+            Transform::from_xyz(camera.x, camera.y, 0.),
+        ));
+        spawn_world_in(
+            &mut app,
+            "worlds/orthogonal.world",
+            TiledWorldChunking::new(3000., 3000.),
+        );
+
+        let events = app.world().resource::<Events<TiledMapSpawned>>();
+        let order = events
+            .get_cursor()
+            .read(events)
+            .map(|e| e.map_index)
+            .collect::<Vec<_>>();
+        assert_eq!(order.len(), 25);
+        assert_eq!(order.first(), Some(&24));
+        assert_eq!(order.last(), Some(&0));
+        // Maps are 512x512 pixels: compare distances between the camera and their centers
+        let distance = |idx: usize| {
+            let center = Vec2::new(
+                (idx % 5) as f32 * 512. + 256.,
+                (4 - idx / 5) as f32 * 512. + 256.,
+            );
+            camera.distance_squared(center)
+        };
+        assert!(order.windows(2).all(|w| distance(w[0]) <= distance(w[1])));
+    }
+
+    fn spawned_maps(app: &App, world_entity: Entity) -> Vec<usize> {
+        let mut maps = app
+            .world()
+            .get::<TiledWorldStorage>(world_entity)
+            .expect("world should be spawned")
+            .spawned_maps
+            .keys()
+            .copied()
+            .collect::<Vec<_>>();
+        maps.sort();
+        maps
+    }
+
+    #[test]
+    fn chunking_reacts_to_cameras_changes() {
+        let (mut app, world_entity) = spawn_world(
+            "worlds/orthogonal.world",
+            TiledWorldChunking::new(100., 100.),
+        );
+        let spawned_maps = |app: &App| spawned_maps(app, world_entity);
+        assert!(spawned_maps(&app).is_empty());
+
+        // Camera in the middle of the top-left map, which is the first one in the file
+        let camera = app
+            .world_mut()
+            .spawn((Camera::default(), Transform::from_xyz(256., 2304., 0.)))
+            .id();
+        app.update();
+        assert_eq!(spawned_maps(&app), vec![0]);
+
+        // Nothing changed: maps are left untouched
+        app.update();
+        assert_eq!(spawned_maps(&app), vec![0]);
+
+        app.world_mut()
+            .get_mut::<Transform>(camera)
+            .unwrap()
+            .translation
+            .x += 512.;
+        app.update();
+        assert_eq!(spawned_maps(&app), vec![1]);
+
+        app.world_mut().despawn(camera);
+        app.update();
+        assert!(spawned_maps(&app).is_empty());
+    }
+
+    #[test]
+    fn chunking_despawn_margin_keeps_maps_spawned() {
+        let (mut app, world_entity) = spawn_world(
+            "worlds/orthogonal.world",
+            (
+                TiledWorldChunking::new(100., 100.),
+                TiledWorldChunkingMargins {
+                    preload: None,
+                    despawn: Some(Vec2::splat(200.)),
+                },
             ),
         );
+        let move_camera_to = {
+            let camera = app
+                .world_mut()
+                .spawn((Camera::default(), Transform::from_xyz(256., 2304., 0.)))
+                .id();
+            move |app: &mut App, x: f32| {
+                app.world_mut()
+                    .get_mut::<Transform>(camera)
+                    .unwrap()
+                    .translation
+                    .x = x;
+                app.update();
+                spawned_maps(app, world_entity)
+            }
+        };
+
+        // Top row maps are 512 pixels wide: map 0 spans from x = 0 to x = 512
+        assert_eq!(move_camera_to(&mut app, 256.), vec![0]);
+        // The camera crossed the border: map 1 is spawned but map 0 is still in the despawn area
+        assert_eq!(move_camera_to(&mut app, 700.), vec![0, 1]);
+        // Map 2 is in the despawn area, but was never in the spawn area
+        assert_eq!(move_camera_to(&mut app, 900.), vec![1]);
+        // Going back and forth across the border does not despawn maps
+        assert_eq!(move_camera_to(&mut app, 450.), vec![0, 1]);
+        assert_eq!(move_camera_to(&mut app, 700.), vec![0, 1]);
     }
 }